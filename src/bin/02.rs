@@ -1,42 +1,46 @@
-i18n_puzzles::solution!(2);
+i18n_puzzles::solution!(stream 2);
+use chrono::TimeZone;
+use chrono_tz::Tz;
 use hashbrown::HashMap;
+use i18n_puzzles::template::Input;
+use i18n_puzzles::utils::dateformat::format_pattern;
+use i18n_puzzles::utils::locale::Locale;
+use i18n_puzzles::PuzzleError;
 use time::format_description::well_known::Iso8601;
-use time::{OffsetDateTime, format_description};
+use time::OffsetDateTime;
 
-fn find_timestamp(input: &str) -> Option<i64> {
+// e.g. 2019-06-05T12:15:00+00:00
+const ISO_8601: &str = "yyyy-MM-dd'T'HH:mm:ssxxx";
+
+fn find_timestamp(input: &Input) -> Result<Option<i64>, PuzzleError> {
     let mut timestamps: HashMap<i64, u8> = HashMap::new();
 
-    for line in input.lines() {
-        let date = OffsetDateTime::parse(line, &Iso8601::DEFAULT).unwrap();
+    for (i, line) in input.lines()?.enumerate() {
+        let line = line?;
+        let date = OffsetDateTime::parse(&line, &Iso8601::DEFAULT)
+            .map_err(|e| PuzzleError::new(format!("line {}: {e}", i + 1)))?;
         let timestamp = date.unix_timestamp();
         let entry = timestamps.entry(timestamp);
         *entry.or_default() += 1;
 
         if timestamps[&timestamp] == 4 {
-            return Some(timestamp);
+            return Ok(Some(timestamp));
         }
     }
 
-    None
+    Ok(None)
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    let timestamp = find_timestamp(input)?;
-
-    let result = OffsetDateTime::from_unix_timestamp(timestamp).unwrap();
-
-    let format = format_description::parse(
-        "[year]-[month]-[day]T[hour]:[minute]:[second][offset_hour \
-             sign:mandatory]:[offset_minute]",
-    )
-    .unwrap();
+pub fn part_one(input: &Input) -> Result<String, PuzzleError> {
+    let timestamp =
+        find_timestamp(input)?.ok_or_else(|| PuzzleError::new("no timestamp occurs four times"))?;
 
-    let iso8601_string = result.format(&format).unwrap();
+    let result = Tz::UTC.timestamp_opt(timestamp, 0).unwrap();
 
-    Some(iso8601_string)
+    Ok(format_pattern(&result, ISO_8601, Locale::English))
 }
 
-pub fn part_two(_input: &str) -> Option<u64> {
+pub fn part_two(_input: &Input) -> Option<u64> {
     None
 }
 
@@ -46,13 +50,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&i18n_puzzles::template::read_file("examples", DAY));
-        assert_eq!(result, Some(String::from("2019-06-05T12:15:00+00:00")));
+        let input = i18n_puzzles::template::read_file("examples", DAY);
+        let result = part_one(&Input::Text(&input));
+        assert_eq!(result, Ok(String::from("2019-06-05T12:15:00+00:00")));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&i18n_puzzles::template::read_file("examples", DAY));
+        let input = i18n_puzzles::template::read_file("examples", DAY);
+        let result = part_two(&Input::Text(&input));
         assert_eq!(result, None);
     }
 }
@@ -1,23 +1,26 @@
 i18n_puzzles::solution!(4);
-use i18n_puzzles::utils::time::parse_date_with_iana;
+use i18n_puzzles::utils::locale::Locale;
+use i18n_puzzles::utils::time::{parse_natural_date, Disambiguation};
+use i18n_puzzles::PuzzleError;
 use regex::Regex;
 
 // code mostly stolen from bramhaag
-const DATE_FORMAT: &str = "%b %d, %Y, %H:%M";
+// Dates are like `Mar 04, 2020, 10:00`
+const LOCALES: [Locale; 1] = [Locale::English];
 
-pub fn part_one(input: &str) -> Option<i64> {
+pub fn part_one(input: &str) -> Result<i64, PuzzleError> {
     let pattern = Regex::new(r"Departure:\s+(.*?)\s+(.*)\nArrival:\s+(.*?)\s+(.*)\n").unwrap();
 
-    let total_minutes = pattern
+    pattern
         .captures_iter(&input)
         .map(|cap| {
-            let dep = parse_date_with_iana(&cap[2], &cap[1], DATE_FORMAT);
-            let arr = parse_date_with_iana(&cap[4], &cap[3], DATE_FORMAT);
-            (arr.timestamp() - dep.timestamp()) / 60
+            let dep = parse_natural_date(&cap[2], &cap[1], &LOCALES, Disambiguation::Reject)
+                .map_err(|e| e.locate(input, &cap[2]))?;
+            let arr = parse_natural_date(&cap[4], &cap[3], &LOCALES, Disambiguation::Reject)
+                .map_err(|e| e.locate(input, &cap[4]))?;
+            Ok((arr.timestamp() - dep.timestamp()) / 60)
         })
-        .sum();
-
-    Some(total_minutes)
+        .sum()
 }
 
 pub fn part_two(_input: &str) -> Option<u64> {
@@ -31,7 +34,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&i18n_puzzles::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result.ok(), None);
     }
 
     #[test]
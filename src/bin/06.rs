@@ -1,6 +1,5 @@
 i18n_puzzles::solution!(6);
-use encoding::{Encoding, all::ISO_8859_1};
-use std::str::from_utf8;
+use i18n_puzzles::utils::mojibake::Codec;
 
 struct Clue {
     known: (usize, char),
@@ -8,11 +7,9 @@ struct Clue {
 }
 
 fn fix(word: String) -> String {
-    let fixed = ISO_8859_1
-        .encode(&word, encoding::EncoderTrap::Strict)
-        .unwrap();
+    let fixed = Codec::Latin1.encode(&word).unwrap();
 
-    from_utf8(&fixed).unwrap().to_string()
+    String::from_utf8(fixed).unwrap()
 }
 
 fn parse_input(input: &str) -> (Vec<String>, Vec<Clue>) {
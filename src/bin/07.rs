@@ -1,18 +1,22 @@
 i18n_puzzles::solution!(7);
-use chrono::{DateTime, FixedOffset, Offset, TimeZone, Timelike};
+use chrono::{DateTime, FixedOffset, TimeZone, Timelike};
 use chrono_tz::Tz;
+use i18n_puzzles::utils::time::resolve_local;
 use itertools::Itertools;
 use std::str::FromStr;
 
 fn determine_iana_from_options(date: DateTime<FixedOffset>, options: &[String]) -> Option<String> {
-    let offset = date.offset().fix();
-    let naive = date.naive_utc();
+    let naive = date.naive_local();
 
     for option in options {
         let timezone = Tz::from_str(option).unwrap();
-        let guess = timezone.from_utc_datetime(&naive).offset().fix();
 
-        if guess == offset {
+        // The local time may be ambiguous, any of its readings having the offset of the date will do
+        let Some(local) = resolve_local(&timezone, &naive) else {
+            continue;
+        };
+
+        if local.with_offset(*date.offset()).is_some() {
             return Some(option.clone());
         }
     }
@@ -79,4 +83,9 @@ mod tests {
         let result = part_two(&i18n_puzzles::template::read_file("examples", DAY));
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn test_examples() {
+        i18n_puzzles::template::check_examples(&SOLUTION);
+    }
 }
@@ -1,6 +1,7 @@
 i18n_puzzles::solution!(9);
-use chrono::{NaiveDateTime, format::ParseErrorKind};
+use chrono::{Datelike, NaiveDate};
 use hashbrown::HashMap;
+use i18n_puzzles::utils::dateorder::infer_formats;
 
 fn parse_input(input: &str) -> HashMap<String, Vec<String>> {
     let mut authors_map = HashMap::new();
@@ -18,60 +19,9 @@ fn parse_input(input: &str) -> HashMap<String, Vec<String>> {
     authors_map
 }
 
-#[derive(PartialEq)]
-enum Field {
-    Day,
-    Month,
-    Year,
-}
-
-type Format = [Field; 3];
-
-const DATE_FORMATS: [Format; 6] = [
-    [Field::Day, Field::Month, Field::Year],
-    [Field::Day, Field::Year, Field::Month],
-    [Field::Month, Field::Day, Field::Year],
-    [Field::Month, Field::Year, Field::Day],
-    [Field::Year, Field::Day, Field::Month],
-    [Field::Year, Field::Month, Field::Day],
-];
-
-fn format_to_string(format: &Format) -> String {
-    format
-        .iter()
-        .map(|field| match field {
-            Field::Day => "%d",
-            Field::Month => "%m",
-            Field::Year => "%y",
-        })
-        .collect::<Vec<&str>>()
-        .join("-")
-}
-
-fn get_indices(format: &Format) -> (usize, usize, usize) {
-    let year = format.iter().position(|f| *f == Field::Year).unwrap();
-    let month = format.iter().position(|f| *f == Field::Month).unwrap();
-    let day = format.iter().position(|f| *f == Field::Day).unwrap();
-
-    (year, month, day)
-}
-
-fn is_viable_format(format: &Format, dates: &[String]) -> bool {
-    let format_str = format_to_string(format);
-
-    for date_str in dates {
-        let naive = NaiveDateTime::parse_from_str(date_str, &format_str);
-
-        match naive {
-            Ok(_) => (),
-            Err(e) => match e.kind() {
-                ParseErrorKind::NotEnough => (), // We count this as a success
-                _ => return false,
-            },
-        }
-    }
-
-    true
+// Nine-eleven, in whichever century two-digit years are read
+fn is_nine_eleven(date: NaiveDate) -> bool {
+    date.year() % 100 == 1 && date.month() == 9 && date.day() == 11
 }
 
 pub fn part_one(input: &str) -> Option<String> {
@@ -79,15 +29,14 @@ pub fn part_one(input: &str) -> Option<String> {
     let mut result = vec![];
 
     for (author, dates) in authors {
-        let date_format = DATE_FORMATS.iter().find(|f| is_viable_format(f, &dates))?;
-        let (year, month, day) = get_indices(date_format);
-
-        for date in dates {
-            let date: Vec<_> = date.split('-').collect();
-            if date[year] == "01" && date[month] == "09" && date[day] == "11" {
-                result.push(author.clone());
-                break;
-            }
+        let format = infer_formats(&dates).into_iter().next()?.format;
+
+        if dates
+            .iter()
+            .filter_map(|date| format.parse(date))
+            .any(is_nine_eleven)
+        {
+            result.push(author);
         }
     }
 
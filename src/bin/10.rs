@@ -1,4 +1,5 @@
 use hashbrown::HashMap;
+use i18n_puzzles::PuzzleError;
 use rayon::prelude::*;
 use unicode_normalization::UnicodeNormalization;
 use unicode_segmentation::UnicodeSegmentation;
@@ -17,14 +18,18 @@ struct Attempt {
     password: String,
 }
 
-fn parse_input(input: &str) -> (Vec<Entry>, Vec<Attempt>) {
+fn parse_input(input: &str) -> Result<(Vec<Entry>, Vec<Attempt>), PuzzleError> {
     let mut entries = vec![];
     let mut attempts = vec![];
 
-    let (entries_str, attempts_str) = input.split_once("\n\n").unwrap();
+    let (entries_str, attempts_str) = input
+        .split_once("\n\n")
+        .ok_or_else(|| PuzzleError::new("expected a blank line between entries and attempts"))?;
 
     for line in entries_str.lines() {
-        let (username, hash) = line.split_once(' ').unwrap();
+        let (username, hash) = line
+            .split_once(' ')
+            .ok_or_else(|| PuzzleError::at(input, line, "expected `<username> <hash>`"))?;
 
         entries.push(Entry {
             username: username.to_string(),
@@ -33,7 +38,9 @@ fn parse_input(input: &str) -> (Vec<Entry>, Vec<Attempt>) {
     }
 
     for line in attempts_str.lines() {
-        let (username, password) = line.split_once(' ').unwrap();
+        let (username, password) = line
+            .split_once(' ')
+            .ok_or_else(|| PuzzleError::at(input, line, "expected `<username> <password>`"))?;
 
         attempts.push(Attempt {
             username: username.to_string(),
@@ -41,7 +48,7 @@ fn parse_input(input: &str) -> (Vec<Entry>, Vec<Attempt>) {
         });
     }
 
-    (entries, attempts)
+    Ok((entries, attempts))
 }
 
 fn has_accent(c: &str) -> bool {
@@ -75,8 +82,8 @@ fn get_variations(input: &str, mut base: String) -> Vec<String> {
     vec![base]
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (entries, attempts) = parse_input(input);
+pub fn part_one(input: &str) -> Result<u64, PuzzleError> {
+    let (entries, attempts) = parse_input(input)?;
 
     let cracked_passwords = std::sync::Mutex::new(HashMap::new());
 
@@ -113,7 +120,7 @@ pub fn part_one(input: &str) -> Option<u64> {
         })
         .count();
 
-    Some(total as u64)
+    Ok(total as u64)
 }
 
 pub fn part_two(_input: &str) -> Option<u64> {
@@ -127,7 +134,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&i18n_puzzles::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4));
+        assert_eq!(result, Ok(4));
     }
 
     #[test]
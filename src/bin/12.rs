@@ -1,3 +1,4 @@
+use i18n_puzzles::PuzzleError;
 use unicode_normalization::UnicodeNormalization;
 use unicode_segmentation::UnicodeSegmentation;
 
@@ -10,21 +11,26 @@ struct Contact {
     number: u64,
 }
 
-fn parse_input(input: &str) -> Vec<Contact> {
+fn parse_input(input: &str) -> Result<Vec<Contact>, PuzzleError> {
     let mut contacts = vec![];
 
     for line in input.lines() {
-        let (last, rest) = line.split_once(", ").unwrap();
-        let (first, number) = rest.split_once(": ").unwrap();
+        let (last, first, number) = line
+            .split_once(", ")
+            .and_then(|(last, rest)| Some((last, rest.split_once(": ")?)))
+            .map(|(last, (first, number))| (last, first, number))
+            .ok_or_else(|| PuzzleError::at(input, line, "expected `<last>, <first>: <number>`"))?;
 
         contacts.push(Contact {
             first: first.to_string(),
             last: last.to_string(),
-            number: number.parse().unwrap(),
+            number: number
+                .parse()
+                .map_err(|e| PuzzleError::at(input, number, e))?,
         });
     }
 
-    contacts
+    Ok(contacts)
 }
 
 fn apply_english(word: &String) -> String {
@@ -114,8 +120,8 @@ fn apply_dutch(word: &String) -> String {
     word
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let contacts = parse_input(input);
+pub fn part_one(input: &str) -> Result<u64, PuzzleError> {
+    let contacts = parse_input(input)?;
 
     let mut english_contacts: Vec<Contact> = contacts
         .iter()
@@ -160,7 +166,7 @@ pub fn part_one(input: &str) -> Option<u64> {
 
     let result = english_result * swedish_result * dutch_result;
 
-    Some(result as u64)
+    Ok(result)
 }
 
 pub fn part_two(_input: &str) -> Option<u64> {
@@ -174,7 +180,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&i18n_puzzles::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1885816494308838));
+        assert_eq!(result, Ok(1885816494308838));
     }
 
     #[test]
@@ -1,7 +1,16 @@
 i18n_puzzles::solution!(13);
-use encoding::{Encoding, all::ISO_8859_1};
+use i18n_puzzles::utils::encoding::{detect, Charset};
+use i18n_puzzles::utils::parse::parse_hex;
 use unicode_segmentation::UnicodeSegmentation;
 
+// The charsets of the words, with or without a BOM
+const CHARSETS: [Charset; 4] = [
+    Charset::Utf8,
+    Charset::Utf16Le,
+    Charset::Utf16Be,
+    Charset::Latin1,
+];
+
 #[derive(Debug)]
 struct Clue {
     known: (usize, String),
@@ -11,19 +20,7 @@ struct Clue {
 fn parse_input(input: &str) -> (Vec<String>, Vec<Clue>) {
     let (words_str, crossword_str) = input.split_once("\n\n").unwrap();
 
-    let words = words_str
-        .lines()
-        .map(|s| {
-            // Remove BOMs
-            if s.starts_with("feff") || s.starts_with("fffe") {
-                return s[4..].to_string();
-            } else if s.starts_with("efbbbf") {
-                return s[6..].to_string();
-            }
-
-            s.to_string()
-        })
-        .collect();
+    let words = words_str.lines().map(|s| s.to_string()).collect();
 
     let mut clues = vec![];
 
@@ -44,64 +41,29 @@ fn parse_input(input: &str) -> (Vec<String>, Vec<Clue>) {
     (words, clues)
 }
 
-fn hex_to_bytes(hex_str: &str) -> Vec<u8> {
-    (0..hex_str.len())
-        .step_by(2)
-        .map(|i| u8::from_str_radix(&hex_str[i..i + 2], 16).unwrap())
-        .collect()
-}
-
 pub fn part_one(input: &str) -> Option<u64> {
     let (words, clues) = parse_input(input);
 
     let mut total = 0;
 
-    'words: for (i, word) in words.iter().enumerate() {
-        let bytes = hex_to_bytes(&word);
-
-        let utf8 = String::from_utf8(bytes.clone()).unwrap_or_default();
-
-        let double_bytes_be: Vec<u16> = bytes
-            .chunks_exact(2)
-            .map(|c| ((c[0] as u16) << 8) + c[1] as u16)
-            .collect();
-        let utf16_be = String::from_utf16(&double_bytes_be).unwrap_or_default();
-
-        let double_bytes_le: Vec<u16> = bytes
-            .chunks_exact(2)
-            .map(|c| ((c[1] as u16) << 8) + c[0] as u16)
-            .collect();
-        let utf16_le = String::from_utf16(&double_bytes_le).unwrap_or_default();
-
-        let latin = ISO_8859_1
-            .decode(&bytes, encoding::DecoderTrap::Strict)
-            .unwrap_or_default();
-
-        let candidates = [utf8, utf16_be, utf16_le, latin];
-
-        for clue in &clues {
-            for candidate in &candidates {
-                // Latin decoding works even when we have weird results like Ã¶
-                // luckily these can be filtered out by checking if we end up with uppercase
-                // letters. This feels hacky but I don't know how else to solve this
-                let has_uppercase = candidate.chars().filter(|c| c.is_uppercase()).count() > 0;
-                if has_uppercase {
-                    continue;
-                }
-
-                let graphemes: Vec<&str> = candidate.graphemes(true).collect();
-                if graphemes.len() != clue.length {
-                    continue;
-                }
-
-                if graphemes[clue.known.0] != clue.known.1 {
-                    continue;
-                }
-
-                total += i + 1;
-                // println!("Match: {}, {}", candidate, j);
-                continue 'words;
-            }
+    for (i, word) in words.iter().enumerate() {
+        let Some(bytes) = parse_hex(word) else {
+            continue;
+        };
+
+        // NOTE: the legacy charsets decode any bytes, so they would match clues by chance
+        let Some(candidate) = detect(&bytes)
+            .into_iter()
+            .find(|c| CHARSETS.contains(&c.charset))
+        else {
+            continue;
+        };
+        let graphemes: Vec<&str> = candidate.text.graphemes(true).collect();
+
+        let matches =
+            |clue: &Clue| graphemes.len() == clue.length && graphemes[clue.known.0] == clue.known.1;
+        if clues.iter().any(matches) {
+            total += i + 1;
         }
     }
 
@@ -122,6 +84,13 @@ mod tests {
         assert_eq!(result, Some(47));
     }
 
+    #[test]
+    fn test_part_one_unlikely_charsets() {
+        // `poëem` in UTF-16BE, which windows-1252 reads as 10 characters with `p` second
+        let result = part_one("0070006f00eb0065006d\n\n.p........\n");
+        assert_eq!(result, Some(0));
+    }
+
     #[test]
     fn test_part_two() {
         let result = part_two(&i18n_puzzles::template::read_file("examples", DAY));
@@ -1,67 +1,28 @@
 i18n_puzzles::solution!(19);
-use chrono::DateTime;
 use chrono::Utc;
 use hashbrown::HashMap;
 use hashbrown::HashSet;
-use std::process::Command;
+use i18n_puzzles::utils::time::{parse_local_date, Disambiguation};
+use i18n_puzzles::utils::tzdb;
+use i18n_puzzles::utils::tzif::TzifZone;
 
-pub fn convert_to_utc(
-    project_root: &str,
-    date_str: &str,
-    tz: &str,
-    tz_version: &str,
-) -> Option<String> {
-    // This command uses gdate (date on Linux) to apply the requested zoneinfo to a date
-    // Use `build_tzdb.sh` to build the timezone files (probably run it in the project root)
-    let cmd = format!(
-        "TZ={project_root}/data/timezones/zoneinfo-{tz_version}/usr/share/zoneinfo/{tz} gdate -d \"{date_str}\" +\"%Y-%m-%dT%H:%M:%S%z\"",
-    );
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
 
-    let date_output = match Command::new("sh").arg("-c").arg(&cmd).output() {
-        Ok(output) => output,
-        Err(e) => {
-            eprintln!("Command execution failed: {}", e);
-            eprintln!("Command was: {}", cmd);
-            return None;
-        }
-    };
-
-    if date_output.status.success() {
-        let result = String::from_utf8_lossy(&date_output.stdout)
-            .trim()
-            .to_string();
-
-        // Parse the ISO8601 date string
-        let date = DateTime::parse_from_str(&result, "%Y-%m-%dT%H:%M:%S%z").ok()?;
-
-        // Convert to UTC and format back to ISO8601
-        let utc_date = date.with_timezone(&Utc);
-        let result = utc_date.format("%Y-%m-%dT%H:%M:%S+00:00").to_string();
-
-        return Some(result);
-    }
+pub fn convert_to_utc(zone: &TzifZone, date_str: &str) -> Option<String> {
+    // Local times skipped by a transition fail, like they did with `date`
+    let date = parse_local_date(date_str, zone, DATE_FORMAT, Disambiguation::Earliest).ok()?;
 
-    // eprint!("{}", String::from_utf8(date_output.stderr).ok()?);
-
-    None
-}
-
-fn get_project_root() -> String {
-    // Find the project root folder, hopefully the current working directory
-    // Why? I couldn't get relative paths working for the TZ=... declaration
-    let output = Command::new("sh")
-        .arg("-c")
-        .arg("pwd")
-        .output()
-        .expect("Failed to execute pwd command");
-
-    String::from_utf8_lossy(&output.stdout).trim().to_string()
+    // Convert to UTC and format back to ISO8601
+    let utc_date = date.with_timezone(&Utc);
+    Some(utc_date.format("%Y-%m-%dT%H:%M:%S+00:00").to_string())
 }
 
+// The tzdb versions used by the research stations, fetch and compile them with
+// `./build_tzdb.sh 2018c 2018g 2021b 2023d`
 const VERSIONS: [&str; 4] = ["2018c", "2018g", "2021b", "2023d"];
 
 pub fn part_one(input: &str) -> Option<String> {
-    let project_root = get_project_root();
+    let registry = tzdb::installed().ok()?;
 
     // Hashmap of UTC date to set of research stations
     let mut map = HashMap::new();
@@ -72,7 +33,9 @@ pub fn part_one(input: &str) -> Option<String> {
         research_stations.insert(tz);
 
         for tz_version in VERSIONS {
-            match convert_to_utc(&project_root, date_str, tz, tz_version) {
+            let zone = registry.zone(tz_version, tz).ok();
+
+            match zone.and_then(|zone| convert_to_utc(&zone, date_str)) {
                 Some(date) => {
                     map.entry(date).or_insert(HashSet::new()).insert(tz);
                 }
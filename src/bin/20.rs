@@ -1,4 +1,7 @@
 i18n_puzzles::solution!(20);
+use i18n_puzzles::utils::bom::strip;
+use i18n_puzzles::utils::utf16::{self, ByteOrder};
+use i18n_puzzles::utils::utf8::{decode, Strictness, HIGH_SURROGATES};
 
 fn decode_base64(input: &str) -> Vec<u8> {
     input
@@ -35,49 +38,7 @@ fn unpack_base64(base64: &[u8]) -> Vec<u8> {
     bytes
 }
 
-fn to_utf16_le(bytes: &[u8]) -> Vec<u16> {
-    bytes
-        .chunks_exact(2)
-        .map(|c| ((c[1] as u16) << 8) + c[0] as u16)
-        .collect()
-}
-
-fn unpack_utf16_le(bytes: &[u16]) -> Vec<u8> {
-    let mask = 0b1111111111;
-    // Extract 20 bit sequences from UTF-16 surrogate pairs
-    let mut big_bytes: Vec<u32> = vec![];
-
-    let mut i = 0;
-    while i < bytes.len() {
-        let big_byte = match bytes[i] {
-            // Extract code point from surrogate pair
-            // like so:
-            // 110110_aaaaaaaaaa
-            // 110111_bbbbbbbbbb
-            0b110110_0000000000..=0b110110_1111111111 => {
-                // Real input seems to have a dangling surrogate pair?
-                if i + 1 == bytes.len() {
-                    break;
-                }
-
-                let a = (bytes[i] as u32 & mask) << 10;
-                let b = bytes[i + 1] as u32 & mask;
-                i += 2;
-
-                (a | b) + 0x10000
-            }
-            // Anything else gets treated as a 20 bit sequence
-            _ => {
-                let a = bytes[i] as u32;
-                i += 1;
-
-                a
-            }
-        };
-
-        big_bytes.push(big_byte);
-    }
-
+fn unpack_20_bit(big_bytes: &[u32]) -> Vec<u8> {
     // Split two u32 into five u8 (each u32 holds 20 bits)
     big_bytes
         .chunks_exact(2)
@@ -96,62 +57,6 @@ fn unpack_utf16_le(bytes: &[u16]) -> Vec<u8> {
         .collect()
 }
 
-fn handle_utf8_bytes(bytes: &[u8], length: usize) -> u32 {
-    let mut big_byte: u32 = 0;
-
-    for i in (1..length).rev() {
-        big_byte |= (bytes[i] as u32 & 0b111111) << ((length - 1 - i) * 6);
-    }
-
-    big_byte
-}
-
-fn extract_code_points_from_invalid_utf8(bytes: &[u8]) -> Vec<u32> {
-    // This spec seems to allow up to 6 byte long sequences
-    // e.g.
-    // 1111110_0
-    // 10_001110
-    // 10_101010
-    // 10_101010
-    // 10_101010
-    // 10_101110
-
-    // Extract code points
-    let mut code_points = vec![];
-
-    let mut i = 0;
-    while i < bytes.len() {
-        match bytes[i] {
-            // Length 6
-            0b111111_00..=0b111111_11 => {
-                let l = 6;
-                // There's one truncated entry near the end?
-                if i + l >= bytes.len() {
-                    return code_points;
-                }
-
-                let mut big_byte = handle_utf8_bytes(&bytes[i..i + l], l);
-                big_byte |= ((bytes[i] & 0b11) as u32) << ((l - 1) * 6);
-                code_points.push(big_byte);
-                i += l;
-            }
-            // Length 5
-            0b11111_000..=0b11111_111 => {
-                let l = 5;
-
-                let mut big_byte = handle_utf8_bytes(&bytes[i..i + l], l);
-                big_byte |= ((bytes[i] & 0b111) as u32) << ((l - 1) * 6);
-                code_points.push(big_byte);
-                i += l;
-            }
-            // The data only has the above cases
-            _ => i += 1,
-        }
-    }
-
-    code_points
-}
-
 fn extract_utf8_bytes_from_code_points(code_points: &[u32]) -> Vec<u8> {
     let mut bytes = vec![];
 
@@ -183,11 +88,23 @@ pub fn part_one(input: &str) -> Option<String> {
     let base64 = decode_base64(input);
     let unpacked = unpack_base64(&base64);
 
-    // Skip BOM
-    let utf16_le_bytes = to_utf16_le(&unpacked[2..]);
+    // Base64 unpacks to multiples of 3 bytes, so an odd one may be left after the BOM
+    let bytes = strip(&unpacked);
+    let utf16 = utf16::decode(&bytes[..bytes.len() / 2 * 2], ByteOrder::Little).unwrap();
+
+    // Surrogate pairs hold 20 bits and the other units fewer. Real input seems to end with a
+    // dangling surrogate, which holds none.
+    let mut big_bytes = utf16.code_points();
+    if big_bytes
+        .last()
+        .is_some_and(|c| HIGH_SURROGATES.contains(c))
+    {
+        big_bytes.pop();
+    }
 
-    let invalid_utf8 = unpack_utf16_le(&utf16_le_bytes);
-    let code_points = extract_code_points_from_invalid_utf8(&invalid_utf8);
+    let invalid_utf8 = unpack_20_bit(&big_bytes);
+    // The bytes are in the original UTF-8 with up to 6 bytes per code point
+    let code_points = decode(&invalid_utf8, Strictness::Rfc2279).code_points;
     let utf8_bytes = extract_utf8_bytes_from_code_points(&code_points);
 
     let answer = String::from_utf8_lossy(&utf8_bytes).to_string();
@@ -0,0 +1,197 @@
+/// Errors for malformed puzzle inputs, pointing at the offending part of the input.
+use std::{error::Error, fmt::Display, io};
+
+/// Position of an error in a puzzle input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Location {
+    /// Line number, starting at 1.
+    pub line: usize,
+    /// Column in characters, starting at 1.
+    pub column: usize,
+    /// Number of characters the error spans, at least 1.
+    pub width: usize,
+    /// The line of the input the error is located in.
+    pub snippet: String,
+}
+
+impl Location {
+    /// Locate the byte `offset` of `input`, spanning `len` bytes.
+    fn new(input: &str, offset: usize, len: usize) -> Self {
+        let offset = floor_char_boundary(input, offset.min(input.len()));
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        let snippet = input[line_start..line_end].trim_end_matches('\r');
+
+        let span_end = floor_char_boundary(input, (offset + len).min(line_start + snippet.len()));
+
+        Location {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            width: input[offset..span_end.max(offset)].chars().count().max(1),
+            snippet: snippet.to_string(),
+        }
+    }
+}
+
+fn floor_char_boundary(s: &str, mut index: usize) -> usize {
+    while !s.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+/// An error of a solution, usually caused by malformed input.
+///
+/// Errors created with [`PuzzleError::at`] point at the offending part of the input and display
+/// it along with its line and column:
+///
+/// ```text
+/// line 3, column 12: invalid digit found in string
+///   3 | Doe, John: 12a4
+///     |            ^^^^
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PuzzleError {
+    pub message: String,
+    pub location: Option<Location>,
+}
+
+impl PuzzleError {
+    /// Creates an error without a location.
+    pub fn new(message: impl Display) -> Self {
+        PuzzleError {
+            message: message.to_string(),
+            location: None,
+        }
+    }
+
+    /// Creates an error pointing at `span`, which should be a slice of `input`.
+    /// If it is not, the first occurrence of `span` in `input` is used.
+    pub fn at(input: &str, span: &str, message: impl Display) -> Self {
+        Self::new(message).locate(input, span)
+    }
+
+    /// Creates an error pointing at the byte `offset` of `input`.
+    pub fn at_offset(input: &str, offset: usize, message: impl Display) -> Self {
+        PuzzleError {
+            message: message.to_string(),
+            location: Some(Location::new(input, offset, 1)),
+        }
+    }
+
+    /// Points the error at `span` of `input`, unless it already has a location.
+    /// Useful for errors of helpers that only see a part of the input.
+    #[must_use]
+    pub fn locate(mut self, input: &str, span: &str) -> Self {
+        if self.location.is_some() {
+            return self;
+        }
+
+        let start = input.as_ptr() as usize;
+        let span_start = span.as_ptr() as usize;
+
+        let offset = if span_start >= start && span_start + span.len() <= start + input.len() {
+            Some(span_start - start)
+        } else {
+            input.find(span)
+        };
+
+        self.location = offset.map(|offset| Location::new(input, offset, span.len()));
+        self
+    }
+}
+
+impl Display for PuzzleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some(location) = &self.location else {
+            return write!(f, "{}", self.message);
+        };
+
+        let Location {
+            line,
+            column,
+            width,
+            snippet,
+        } = location;
+
+        let gutter = " ".repeat(line.to_string().len());
+
+        writeln!(f, "line {line}, column {column}: {}", self.message)?;
+        writeln!(f, "  {line} | {snippet}")?;
+        write!(
+            f,
+            "  {gutter} | {}{}",
+            " ".repeat(column - 1),
+            "^".repeat(*width)
+        )
+    }
+}
+
+impl Error for PuzzleError {}
+
+impl From<io::Error> for PuzzleError {
+    fn from(e: io::Error) -> Self {
+        PuzzleError::new(e)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PuzzleError;
+
+    const INPUT: &str = "Smith, Anna: 1234\nDoe, John: 12a4\n";
+
+    #[test]
+    fn locates_spans() {
+        let line = INPUT.lines().nth(1).unwrap();
+        let number = &line[11..];
+
+        let error = PuzzleError::at(INPUT, number, "invalid digit found in string");
+        let location = error.location.as_ref().unwrap();
+
+        assert_eq!(location.line, 2);
+        assert_eq!(location.column, 12);
+        assert_eq!(location.width, 4);
+        assert_eq!(location.snippet, "Doe, John: 12a4");
+
+        assert_eq!(
+            error.to_string(),
+            [
+                "line 2, column 12: invalid digit found in string",
+                "  2 | Doe, John: 12a4",
+                "    |            ^^^^",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn locates_copied_spans() {
+        let error = PuzzleError::at(INPUT, &String::from("John"), "unknown");
+        assert_eq!(error.location.unwrap().column, 6);
+    }
+
+    #[test]
+    fn counts_columns_in_characters() {
+        let input = "Æbleskiver: x";
+        let error = PuzzleError::at_offset(input, input.find('x').unwrap(), "expected a number");
+        assert_eq!(error.location.unwrap().column, 13);
+    }
+
+    #[test]
+    fn keeps_existing_locations() {
+        let error = PuzzleError::at(INPUT, "Doe", "unknown").locate(INPUT, "Smith");
+        assert_eq!(error.location.unwrap().line, 2);
+    }
+
+    #[test]
+    fn displays_errors_without_location() {
+        let error = PuzzleError::new("input is empty");
+        assert_eq!(error.to_string(), "input is empty");
+    }
+}
@@ -1,4 +1,7 @@
+pub mod error;
 pub mod template;
 pub mod utils;
 
+pub use error::PuzzleError;
+
 // Use this file to add helper functions and additional modules.
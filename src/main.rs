@@ -1,41 +1,70 @@
 use args::{parse, AppArguments};
-use i18n_puzzles::template::commands::{all, /* download,  read ,*/ scaffold, solve, time};
+use i18n_puzzles::template::commands::{
+    all, check_answers, download, read, scaffold, solve, time, tzdb,
+};
 
 #[cfg(feature = "today")]
-use i18n_puzzles::template::Day;
+use i18n_puzzles::template::{Day, Puzzle};
 #[cfg(feature = "today")]
 use std::process;
 
+// NOTE: solutions are compiled into this binary as modules. They are left out of test builds, which
+// would otherwise run every solution's tests twice, and out of DHAT builds, as every solution
+// installs its own global allocator. Use `cargo solve <day> --dhat` to profile a single day.
+#[cfg(not(any(test, feature = "dhat-heap")))]
+mod solutions;
+
+#[cfg(any(test, feature = "dhat-heap"))]
+mod solutions {
+    pub static SOLUTIONS: &[&dyn i18n_puzzles::template::Solution] = &[];
+}
+
 mod args {
-    use i18n_puzzles::template::Day;
-    use std::process;
+    use i18n_puzzles::template::compare::DEFAULT_THRESHOLD;
+    use i18n_puzzles::template::report::Format;
+    use i18n_puzzles::template::runner::RunOptions;
+    use i18n_puzzles::template::{Edition, Puzzle};
+    use std::{path::PathBuf, process, time::Duration};
 
     pub enum AppArguments {
-        // Download {
-        //     day: Day,
-        // },
-        // Read {
-        //     day: Day,
-        // },
+        Download {
+            puzzle: Puzzle,
+            overwrite: bool,
+        },
+        Read {
+            puzzle: Puzzle,
+        },
         Scaffold {
-            day: Day,
-            // download: bool,
+            puzzle: Puzzle,
+            download: bool,
             overwrite: bool,
         },
         Solve {
-            day: Day,
+            puzzle: Puzzle,
             release: bool,
             dhat: bool,
-            // submit: Option<u8>,
+            format: Format,
         },
         All {
-            release: bool,
+            edition: Option<Edition>,
+            format: Format,
         },
         Time {
             all: bool,
-            day: Option<Day>,
+            puzzle: Option<Puzzle>,
+            edition: Option<Edition>,
+            store: bool,
+            compare_threshold: Option<f64>,
+            options: RunOptions,
+        },
+        CheckAnswers {
             store: bool,
         },
+        TzdbBuild {
+            dir: PathBuf,
+            version: Option<String>,
+            out: Option<PathBuf>,
+        },
         #[cfg(feature = "today")]
         Today,
     }
@@ -45,34 +74,68 @@ mod args {
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
-                release: args.contains("--release"),
+                edition: args.opt_value_from_str("--edition")?,
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let compare = args.contains("--compare");
+                let threshold: Option<f64> = args.opt_value_from_str("--threshold")?;
+                let format = args.opt_value_from_str("--format")?.unwrap_or_default();
+                let budget: Option<u64> = args.opt_value_from_str("--budget")?;
+                let edition = args.opt_value_from_str("--edition")?;
+
+                let mut options = RunOptions {
+                    is_timed: true,
+                    format,
+                    ..RunOptions::default()
+                };
+
+                if let Some(millis) = budget {
+                    options.budget = Duration::from_millis(millis);
+                }
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
+                    puzzle: args.opt_free_from_str()?,
+                    edition,
                     store,
+                    compare_threshold: compare.then(|| threshold.unwrap_or(DEFAULT_THRESHOLD)),
+                    options,
                 }
             }
-            // Some("download") => AppArguments::Download {
-            //     day: args.free_from_str()?,
-            // },
-            // Some("read") => AppArguments::Read {
-            //     day: args.free_from_str()?,
-            // },
+            Some("download") => AppArguments::Download {
+                puzzle: args.free_from_str()?,
+                overwrite: args.contains("--overwrite"),
+            },
+            Some("read") => AppArguments::Read {
+                puzzle: args.free_from_str()?,
+            },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                // download: args.contains("--download"),
+                puzzle: args.free_from_str()?,
+                download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                puzzle: args.free_from_str()?,
                 release: args.contains("--release"),
-                // submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+            },
+            Some("check-answers") => AppArguments::CheckAnswers {
+                store: args.contains("--store"),
+            },
+            Some("tzdb") => match args.subcommand()?.as_deref() {
+                Some("build") => AppArguments::TzdbBuild {
+                    version: args.opt_value_from_str("--version")?,
+                    out: args.opt_value_from_str("--out")?,
+                    dir: args.free_from_str()?,
+                },
+                _ => {
+                    eprintln!("Usage: cargo tzdb build <dir> [--version <version>] [--out <dir>]");
+                    process::exit(1);
+                }
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -102,33 +165,55 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            // AppArguments::Download { day } => download::handle(day),
-            // AppArguments::Read { day } => read::handle(day),
+            AppArguments::All { edition, format } => {
+                all::handle(solutions::SOLUTIONS, edition, format);
+            }
+            AppArguments::Time {
+                puzzle,
+                edition,
+                all,
+                store,
+                compare_threshold,
+                options,
+            } => time::handle(
+                solutions::SOLUTIONS,
+                puzzle,
+                edition,
+                all,
+                store,
+                compare_threshold,
+                options,
+            ),
+            AppArguments::Download { puzzle, overwrite } => download::handle(puzzle, overwrite),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
             AppArguments::Scaffold {
-                day,
-                // download,
+                puzzle,
+                download,
                 overwrite,
             } => {
-                scaffold::handle(day, overwrite);
-                // if download {
-                //     download::handle(day);
-                // }
+                scaffold::handle(puzzle, overwrite);
+                if download {
+                    download::handle(puzzle, false);
+                }
             }
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
-                // submit,
-            } => solve::handle(day, release, dhat /*, submit */),
+                format,
+            } => solve::handle(puzzle, release, dhat, format),
+            AppArguments::CheckAnswers { store } => {
+                check_answers::handle(solutions::SOLUTIONS, store);
+            }
+            AppArguments::TzdbBuild { dir, version, out } => tzdb::handle(dir, version, out),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                        let puzzle = Puzzle::from(day);
+                        scaffold::handle(puzzle, false);
+                        download::handle(puzzle, false);
+                        read::handle(puzzle);
                     }
                     None => {
                         eprintln!(
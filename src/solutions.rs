@@ -0,0 +1,67 @@
+//! Registry of all scaffolded solutions, compiled into the runner binary.
+//! This file is generated by `cargo scaffold`, do not edit it by hand.
+use i18n_puzzles::template::Solution;
+
+#[path = "bin/01.rs"]
+mod day_01;
+#[path = "bin/02.rs"]
+mod day_02;
+#[path = "bin/03.rs"]
+mod day_03;
+#[path = "bin/04.rs"]
+mod day_04;
+#[path = "bin/05.rs"]
+mod day_05;
+#[path = "bin/06.rs"]
+mod day_06;
+#[path = "bin/07.rs"]
+mod day_07;
+#[path = "bin/08.rs"]
+mod day_08;
+#[path = "bin/09.rs"]
+mod day_09;
+#[path = "bin/10.rs"]
+mod day_10;
+#[path = "bin/11.rs"]
+mod day_11;
+#[path = "bin/12.rs"]
+mod day_12;
+#[path = "bin/13.rs"]
+mod day_13;
+#[path = "bin/14.rs"]
+mod day_14;
+#[path = "bin/15.rs"]
+mod day_15;
+#[path = "bin/16.rs"]
+mod day_16;
+#[path = "bin/17.rs"]
+mod day_17;
+#[path = "bin/18.rs"]
+mod day_18;
+#[path = "bin/19.rs"]
+mod day_19;
+#[path = "bin/20.rs"]
+mod day_20;
+
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day_01::SOLUTION,
+    &day_02::SOLUTION,
+    &day_03::SOLUTION,
+    &day_04::SOLUTION,
+    &day_05::SOLUTION,
+    &day_06::SOLUTION,
+    &day_07::SOLUTION,
+    &day_08::SOLUTION,
+    &day_09::SOLUTION,
+    &day_10::SOLUTION,
+    &day_11::SOLUTION,
+    &day_12::SOLUTION,
+    &day_13::SOLUTION,
+    &day_14::SOLUTION,
+    &day_15::SOLUTION,
+    &day_16::SOLUTION,
+    &day_17::SOLUTION,
+    &day_18::SOLUTION,
+    &day_19::SOLUTION,
+    &day_20::SOLUTION,
+];
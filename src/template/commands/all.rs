@@ -1,5 +1,17 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::report::Format;
+use crate::template::run_multi::{puzzles_to_run, run_multi};
+use crate::template::runner::RunOptions;
+use crate::template::{Edition, Solution};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(solutions: &[&dyn Solution], edition: Option<Edition>, format: Format) {
+    let options = RunOptions {
+        format,
+        ..RunOptions::default()
+    };
+
+    run_multi(
+        solutions,
+        &puzzles_to_run(solutions, edition).collect(),
+        options,
+    );
 }
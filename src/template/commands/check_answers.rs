@@ -0,0 +1,139 @@
+use std::{fmt::Display, process};
+
+use crate::template::answers::Answers;
+use crate::template::{Input, Puzzle, Solution, ANSI_BOLD, ANSI_RESET};
+
+/// Outcome of checking a single part against its accepted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Fail,
+    /// No accepted answer has been stored for this part.
+    Missing,
+    /// The solution did not produce an answer.
+    Unsolved,
+    /// The solution no longer produces an answer, although one was accepted.
+    Lost,
+    /// The solution returned an error.
+    Error,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Pass => f.write_str("pass"),
+            Status::Fail => f.write_str("FAIL"),
+            Status::Missing => f.write_str("missing"),
+            Status::Unsolved => f.write_str("unsolved"),
+            Status::Lost => f.write_str("LOST"),
+            Status::Error => f.write_str("ERROR"),
+        }
+    }
+}
+
+fn check_part(expected: Option<&String>, actual: Option<&String>) -> Status {
+    match (expected, actual) {
+        (Some(_), None) => Status::Lost,
+        (None, None) => Status::Unsolved,
+        (None, Some(_)) => Status::Missing,
+        (Some(expected), Some(actual)) if expected == actual => Status::Pass,
+        (Some(_), Some(_)) => Status::Fail,
+    }
+}
+
+/// Run every solution in-process and check its answers against `data/answers.json`.
+/// With `store`, answers of parts that have no accepted answer yet are recorded.
+pub fn handle(solutions: &[&dyn Solution], store: bool) {
+    let mut answers = Answers::read_from_file();
+    let mut failures: Vec<(Puzzle, u8)> = vec![];
+    let mut stored = 0;
+
+    let mut solutions = solutions.to_vec();
+    solutions.sort_unstable_by_key(|s| s.puzzle());
+
+    for solution in solutions {
+        let puzzle = solution.puzzle();
+
+        let Ok(input) = Input::open("inputs", puzzle) else {
+            println!("{ANSI_BOLD}Day {puzzle}{ANSI_RESET}  missing input");
+
+            // Accepted answers can't be checked without the input
+            for &part in solution.parts() {
+                if answers.get(puzzle, part).is_some() {
+                    failures.push((puzzle, part));
+                }
+            }
+            continue;
+        };
+
+        let mut line = format!("{ANSI_BOLD}Day {puzzle}{ANSI_RESET}");
+        let mut errors = vec![];
+
+        for &part in solution.parts() {
+            let (status, actual) = match solution.solve_input(part, &input).to_strings() {
+                Ok(actual) => (
+                    check_part(answers.get(puzzle, part), actual.as_ref()),
+                    actual,
+                ),
+                Err(e) => {
+                    errors.push(format!("Part {part}: {e}"));
+                    (Status::Error, None)
+                }
+            };
+
+            line.push_str(&format!("  Part {part}: {status}"));
+
+            match status {
+                Status::Error | Status::Fail | Status::Lost => failures.push((puzzle, part)),
+                Status::Missing if store => {
+                    answers.set(puzzle, part, actual.unwrap());
+                    stored += 1;
+                }
+                _ => {}
+            }
+        }
+
+        println!("{line}");
+
+        for error in errors {
+            println!("{error}");
+        }
+    }
+
+    if stored > 0 {
+        match answers.store_file() {
+            Ok(()) => println!("\nStored {stored} new answer(s)."),
+            Err(e) => eprintln!("\nFailed to store answers: {e}"),
+        }
+    }
+
+    if !failures.is_empty() {
+        let failures: Vec<String> = failures
+            .iter()
+            .map(|(puzzle, part)| format!("day {puzzle} part {part}"))
+            .collect();
+
+        eprintln!(
+            "\nWrong, lost or unchecked answers or errors for {}.",
+            failures.join(", ")
+        );
+        process::exit(1);
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check_part, Status};
+
+    #[test]
+    fn checks_parts() {
+        let answer = "42".to_string();
+        let other = "43".to_string();
+
+        assert_eq!(check_part(Some(&answer), Some(&answer)), Status::Pass);
+        assert_eq!(check_part(Some(&answer), Some(&other)), Status::Fail);
+        assert_eq!(check_part(None, Some(&answer)), Status::Missing);
+        assert_eq!(check_part(Some(&answer), None), Status::Lost);
+        assert_eq!(check_part(None, None), Status::Unsolved);
+    }
+}
@@ -1,14 +1,32 @@
-use crate::template::{aoc_cli, Day};
+use crate::template::puzzle_source::{self, Resource};
+use crate::template::Puzzle;
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
-
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+pub fn handle(puzzle: Puzzle, overwrite: bool) {
+    let source = match puzzle_source::from_env() {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("Failed to set up puzzle source: {e}");
+            process::exit(1);
+        }
     };
+
+    println!("---");
+
+    for resource in [Resource::Input, Resource::Puzzle] {
+        let path = resource.local_path(puzzle);
+
+        match puzzle_source::download(source.as_ref(), resource, puzzle, overwrite) {
+            Ok(true) => println!("🎄 Successfully wrote {path:?}."),
+            Ok(false) => {
+                println!(
+                    "🎄 Skipped {path:?} as it already exists. Pass `--overwrite` to replace it."
+                );
+            }
+            Err(e) => {
+                eprintln!("Failed to download {path:?}: {e}");
+                process::exit(1);
+            }
+        }
+    }
 }
@@ -1,15 +1,27 @@
-use std::process;
+use std::{fs, process};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::puzzle_source::{self, Resource};
+use crate::template::Puzzle;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+pub fn handle(puzzle: Puzzle) {
+    let path = Resource::Puzzle.local_path(puzzle);
+
+    // prefer the local copy, fetch the puzzle if it hasn't been downloaded yet.
+    if let Ok(description) = fs::read_to_string(&path)
+        && !description.is_empty()
+    {
+        println!("{description}");
+        return;
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    let description =
+        puzzle_source::from_env().and_then(|source| source.fetch(Resource::Puzzle, puzzle));
+
+    match description {
+        Ok(description) => println!("{description}"),
+        Err(e) => {
+            eprintln!("Failed to read puzzle: {e}");
+            process::exit(1);
+        }
+    }
 }
@@ -1,10 +1,11 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{registry, Puzzle};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
@@ -20,6 +21,11 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
+    // NOTE: editions other than the default one live in their own data directories.
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,10 +33,21 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// The argument of the `solution!` macro for a puzzle, e.g. `7` or `2026 / 7`.
+fn macro_argument(puzzle: Puzzle) -> String {
+    let day = puzzle.day.into_inner();
+
+    if puzzle.is_default_edition() {
+        day.to_string()
+    } else {
+        format!("{} / {day}", puzzle.edition)
+    }
+}
+
+pub fn handle(puzzle: Puzzle, overwrite: bool) {
+    let input_path = puzzle.data_path("inputs", "txt");
+    let example_path = puzzle.data_path("examples", "txt");
+    let module_path = format!("src/bin/{}.rs", puzzle.bin_name());
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -42,7 +59,7 @@ pub fn handle(day: Day, overwrite: bool) {
 
     match file.write_all(
         MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+            .replace("%PUZZLE%", &macro_argument(puzzle))
             .as_bytes(),
     ) {
         Ok(()) => {
@@ -74,6 +91,19 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
+    match registry::update() {
+        Ok(()) => {
+            println!("Registered solution in \"src/solutions.rs\"");
+        }
+        Err(e) => {
+            eprintln!("Failed to update solution registry: {e}");
+            process::exit(1);
+        }
+    }
+
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!(
+        "🎄 Type `cargo solve {}` to run your solution.",
+        puzzle.bin_name()
+    );
 }
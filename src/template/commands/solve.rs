@@ -1,9 +1,10 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::report::Format;
+use crate::template::Puzzle;
 
-pub fn handle(day: Day, release: bool, dhat: bool /*, submit_part: Option<u8> */) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(puzzle: Puzzle, release: bool, dhat: bool, format: Format) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.bin_name()];
 
     if dhat {
         cmd_args.extend([
@@ -18,10 +19,11 @@ pub fn handle(day: Day, release: bool, dhat: bool /*, submit_part: Option<u8> */
 
     cmd_args.push("--".to_string());
 
-    // if let Some(submit_part) = submit_part {
-    //     cmd_args.push("--submit".to_string());
-    //     cmd_args.push(submit_part.to_string());
-    // }
+    if !format.is_human() {
+        // mirror `--format` flag to the solution binary.
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format.to_string());
+    }
 
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
@@ -1,40 +1,81 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, process};
 
-use crate::template::run_multi::run_multi;
+use crate::template::compare::{compare, has_regressions, print_table};
+use crate::template::run_multi::{puzzles_to_run, run_multi};
+use crate::template::runner::RunOptions;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{readme_benchmarks, Edition, Puzzle, Solution};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(
+    solutions: &[&dyn Solution],
+    puzzle: Option<Puzzle>,
+    edition: Option<Edition>,
+    run_all: bool,
+    store: bool,
+    compare_threshold: Option<f64>,
+    options: RunOptions,
+) {
     let stored_timings = Timings::read_from_file();
 
-    let days_to_run = day.map_or_else(
+    let puzzles = puzzle.map_or_else(
         || {
             if run_all {
-                all_days().collect()
+                puzzles_to_run(solutions, edition).collect()
+            } else if compare_threshold.is_some() {
+                // when comparing, run every day that has a baseline.
+                stored_timings
+                    .data
+                    .iter()
+                    .map(|t| t.puzzle)
+                    .filter(|puzzle| edition.is_none_or(|edition| puzzle.edition == edition))
+                    .collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                puzzles_to_run(solutions, edition)
+                    .filter(|puzzle| !stored_timings.is_day_complete(*puzzle))
                     .collect()
             }
         },
-        |day| HashSet::from([day]),
+        |puzzle| HashSet::from([puzzle]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(solutions, &puzzles, options).unwrap();
+
+    let mut has_regressed = false;
+
+    if let Some(threshold) = compare_threshold {
+        let comparisons = compare(&stored_timings, &timings, threshold);
+        has_regressed = has_regressions(&comparisons);
+
+        if options.format.is_human() {
+            println!();
+            print_table(&comparisons);
+        }
+
+        if has_regressed {
+            eprintln!("\nFound regressions of more than {threshold}% against stored timings.");
+        }
+    }
 
-    if store {
+    // NOTE: never overwrite the baseline with a regressed run.
+    if store && !has_regressed {
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
-        println!();
         match readme_benchmarks::update(merged_timings) {
             Ok(()) => {
-                println!("Stored updated benchmarks.");
+                if options.format.is_human() {
+                    println!();
+                    println!("Stored updated benchmarks.");
+                }
             }
             Err(_) => {
                 eprintln!("Failed to store updated benchmarks.");
             }
         }
     }
+
+    if has_regressed {
+        process::exit(1);
+    }
 }
@@ -0,0 +1,71 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::utils::tzdata::Tzdata;
+use crate::utils::tzdb::DEFAULT_ROOT;
+
+/// The release of a tzdata source directory, read from its `version` file (present since 2016)
+/// or from the directory name, e.g. `tzdata2018c` or `tzdata-2018c`.
+fn detect_version(dir: &Path) -> Option<String> {
+    if let Ok(version) = fs::read_to_string(dir.join("version"))
+        && !version.trim().is_empty()
+    {
+        return Some(version.trim().to_string());
+    }
+
+    let name = dir
+        .canonicalize()
+        .ok()?
+        .file_name()?
+        .to_string_lossy()
+        .to_string();
+    let version = name.strip_prefix("tzdata").unwrap_or(&name);
+    let version = version.trim_start_matches(['-', '_']);
+
+    // NOTE: releases are named like `2018c`, four digits and a letter.
+    let is_release = version.len() == 5
+        && version[..4].bytes().all(|b| b.is_ascii_digit())
+        && version.as_bytes()[4].is_ascii_lowercase();
+
+    is_release.then(|| version.to_string())
+}
+
+/// Compile the tzdata sources in `dir` into `out`, by default the registry directory of its
+/// release, e.g. `data/timezones/zoneinfo-2018c`.
+pub fn handle(dir: PathBuf, version: Option<String>, out: Option<PathBuf>) {
+    let out = match (out, version.or_else(|| detect_version(&dir))) {
+        (Some(out), _) => out,
+        (None, Some(version)) => Path::new(DEFAULT_ROOT).join(format!("zoneinfo-{version}")),
+        (None, None) => {
+            eprintln!(
+                "Could not determine the tzdata release of {}, pass it with `--version <version>`.",
+                dir.display()
+            );
+            process::exit(1);
+        }
+    };
+
+    let tzdata = match Tzdata::read_dir(&dir) {
+        Ok(tzdata) => tzdata,
+        Err(e) => {
+            eprintln!("Failed to read tzdata sources: {e}");
+            process::exit(1);
+        }
+    };
+
+    match tzdata.write_to(&out) {
+        Ok((zones, links)) => {
+            println!(
+                "Compiled {zones} zones and {links} links to {}",
+                out.display()
+            );
+        }
+        Err(e) => {
+            eprintln!("Failed to compile tzdata: {e}");
+            process::exit(1);
+        }
+    }
+}
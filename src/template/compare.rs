@@ -0,0 +1,229 @@
+/// Module that compares benchmark runs against the timings stored in `data/timings.json`.
+use std::{fmt::Display, time::Duration};
+
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Puzzle, ANSI_BOLD, ANSI_RESET};
+
+/// Default relative change (in percent) that is tolerated before a part counts as faster or slower.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Faster,
+    Slower,
+    Unchanged,
+    /// There is no baseline to compare against.
+    New,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Faster => f.write_str("faster"),
+            Verdict::Slower => f.write_str("slower"),
+            Verdict::Unchanged => f.write_str("unchanged"),
+            Verdict::New => f.write_str("new"),
+        }
+    }
+}
+
+/// Represents the comparison of a single part against its baseline.
+#[derive(Clone, Debug)]
+pub struct Comparison {
+    pub puzzle: Puzzle,
+    pub part: u8,
+    pub baseline: Option<Duration>,
+    pub current: Duration,
+    pub verdict: Verdict,
+}
+
+impl Comparison {
+    /// Relative change against the baseline in percent.
+    pub fn delta(&self) -> Option<f64> {
+        let baseline = self.baseline?.as_secs_f64();
+        Some((self.current.as_secs_f64() - baseline) / baseline * 100.0)
+    }
+}
+
+/// Compare every part of `current` against `baseline`.
+/// A part is a regression if it got slower by more than `threshold` percent.
+pub fn compare(baseline: &Timings, current: &Timings, threshold: f64) -> Vec<Comparison> {
+    let mut comparisons = vec![];
+
+    for timing in &current.data {
+        let stored = baseline.data.iter().find(|t| t.puzzle == timing.puzzle);
+
+        for part in [1, 2] {
+            let Some(current) = part_duration(timing, part) else {
+                continue;
+            };
+
+            let baseline = stored.and_then(|t| part_duration(t, part));
+
+            let verdict = match baseline {
+                None => Verdict::New,
+                Some(baseline) => {
+                    let ratio = current.as_secs_f64() / baseline.as_secs_f64();
+
+                    if ratio > 1.0 + threshold / 100.0 {
+                        Verdict::Slower
+                    } else if ratio < 1.0 - threshold / 100.0 {
+                        Verdict::Faster
+                    } else {
+                        Verdict::Unchanged
+                    }
+                }
+            };
+
+            comparisons.push(Comparison {
+                puzzle: timing.puzzle,
+                part,
+                baseline,
+                current,
+                verdict,
+            });
+        }
+    }
+
+    comparisons
+}
+
+/// Returns `true` if any of the parts got slower.
+pub fn has_regressions(comparisons: &[Comparison]) -> bool {
+    comparisons.iter().any(|c| c.verdict == Verdict::Slower)
+}
+
+pub fn print_table(comparisons: &[Comparison]) {
+    println!(
+        "{ANSI_BOLD}{:<7} {:<5} {:>12} {:>12} {:>9}  Result{ANSI_RESET}",
+        "Puzzle", "Part", "Baseline", "Current", "Delta"
+    );
+
+    for comparison in comparisons {
+        let baseline = comparison
+            .baseline
+            .map_or_else(|| "-".into(), |b| format!("{b:.1?}"));
+
+        let delta = comparison
+            .delta()
+            .map_or_else(|| "-".into(), |d| format!("{d:+.1}%"));
+
+        println!(
+            "{:<7} {:<5} {:>12} {:>12} {:>9}  {}",
+            comparison.puzzle.to_string(),
+            comparison.part,
+            baseline,
+            format!("{:.1?}", comparison.current),
+            delta,
+            comparison.verdict
+        );
+    }
+}
+
+/// The duration to compare for a part. Uses the median if statistics were recorded,
+/// falls back to the stored display string for timings recorded before that.
+fn part_duration(timing: &Timing, part: u8) -> Option<Duration> {
+    let (duration_str, stats) = match part {
+        1 => (&timing.part_1, timing.part_1_stats),
+        _ => (&timing.part_2, timing.part_2_stats),
+    };
+
+    match stats {
+        Some(stats) => Some(stats.median),
+        None => parse_duration(duration_str.as_deref()?),
+    }
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.strip_suffix(postfix)?.parse().ok()
+}
+
+/// Parse a duration formatted with `{:?}`, e.g. `1.2ms`.
+fn parse_duration(s: &str) -> Option<Duration> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let nanos = match s {
+        s if s.ends_with("ns") => parse_to_float(s, "ns"),
+        s if s.ends_with("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.ends_with("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }?;
+
+    Some(Duration::from_secs_f64(nanos / 1_000_000_000_f64))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{compare, has_regressions, parse_duration, Verdict};
+    use crate::{
+        puzzle,
+        template::timings::{Timing, Timings},
+        template::Puzzle,
+    };
+
+    fn timing(puzzle: Puzzle, part_1: &str, part_2: Option<&str>) -> Timing {
+        Timing {
+            puzzle,
+            part_1: Some(part_1.into()),
+            part_2: part_2.map(Into::into),
+            part_1_stats: None,
+            part_2_stats: None,
+            total_nanos: 0_f64,
+        }
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("74ns"), Some(Duration::from_nanos(74)));
+        assert_eq!(parse_duration("1.5µs"), Some(Duration::from_nanos(1500)));
+        assert_eq!(parse_duration("2ms"), Some(Duration::from_millis(2)));
+        assert_eq!(parse_duration("4.5s"), Some(Duration::from_millis(4500)));
+        assert_eq!(parse_duration("fast"), None);
+    }
+
+    #[test]
+    fn compares_timings() {
+        let baseline = Timings {
+            data: vec![
+                timing(puzzle!(1), "10ms", Some("10ms")),
+                timing(puzzle!(2), "10ms", None),
+            ],
+        };
+        let current = Timings {
+            data: vec![
+                timing(puzzle!(1), "8ms", Some("10.5ms")),
+                timing(puzzle!(2), "12ms", Some("1ms")),
+            ],
+        };
+
+        let comparisons = compare(&baseline, &current, 10.0);
+        let verdicts: Vec<Verdict> = comparisons.iter().map(|c| c.verdict).collect();
+
+        assert_eq!(
+            verdicts,
+            [
+                Verdict::Faster,
+                Verdict::Unchanged,
+                Verdict::Slower,
+                Verdict::New
+            ]
+        );
+        assert_eq!(comparisons[2].delta().map(f64::round), Some(20.0));
+        assert!(has_regressions(&comparisons));
+    }
+
+    #[test]
+    fn respects_threshold() {
+        let baseline = Timings {
+            data: vec![timing(puzzle!(1), "10ms", None)],
+        };
+        let current = Timings {
+            data: vec![timing(puzzle!(1), "12ms", None)],
+        };
+
+        let comparisons = compare(&baseline, &current, 25.0);
+        assert_eq!(comparisons[0].verdict, Verdict::Unchanged);
+        assert!(!has_regressions(&comparisons));
+    }
+}
@@ -0,0 +1,333 @@
+/// Reads named example inputs of a puzzle along with their expected answers.
+///
+/// Examples live in a directory per day, e.g. `data/examples/07/*.txt`. The expected answers of an
+/// example are either declared in a front-matter block at the start of the file:
+///
+/// ```text
+/// ---
+/// part_1: 866
+/// part_2: 42
+/// ---
+/// <the input>
+/// ```
+///
+/// or in a sidecar file next to it (`dst-gap.expected` for `dst-gap.txt`) holding the same
+/// `key: value` lines. Examples with a sidecar file are read verbatim, which allows inputs that
+/// need to start with e.g. a byte order mark. Parts without an expected answer are not checked.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::{Puzzle, Solution};
+
+static FRONT_MATTER_DELIMITER: &str = "---";
+static SIDECAR_EXTENSION: &str = "expected";
+
+#[derive(Debug)]
+pub enum ExampleError {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl Display for ExampleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExampleError::Parser(e) => write!(f, "{e}"),
+            ExampleError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for ExampleError {
+    fn from(e: io::Error) -> Self {
+        ExampleError::IO(e)
+    }
+}
+
+/// A named example input and the answers it is expected to produce.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    /// File name of the example without its extension.
+    pub name: String,
+    pub input: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Example {
+    pub fn expected(&self, part: u8) -> Option<&String> {
+        match part {
+            1 => self.part_1.as_ref(),
+            _ => self.part_2.as_ref(),
+        }
+    }
+
+    /// Parse an example file, splitting off its front-matter if it has one.
+    pub fn parse(name: &str, content: &str) -> Result<Self, ExampleError> {
+        let mut example = Example {
+            name: name.to_string(),
+            input: content.to_string(),
+            part_1: None,
+            part_2: None,
+        };
+
+        let Some(rest) = strip_line(content, FRONT_MATTER_DELIMITER) else {
+            return Ok(example);
+        };
+
+        let mut offset = 0;
+
+        for line in rest.split_inclusive('\n') {
+            offset += line.len();
+
+            if line.trim_end() == FRONT_MATTER_DELIMITER {
+                example.set_expected(&rest[..offset - line.len()])?;
+                example.input = rest[offset..].to_string();
+                return Ok(example);
+            }
+        }
+
+        Err(ExampleError::Parser(format!(
+            "example `{name}`: front-matter is not closed by `{FRONT_MATTER_DELIMITER}`."
+        )))
+    }
+
+    /// Set the expected answers from `key: value` lines.
+    fn set_expected(&mut self, lines: &str) -> Result<(), ExampleError> {
+        for line in lines.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let Some((key, value)) = line.split_once(':') else {
+                return Err(ExampleError::Parser(format!(
+                    "example `{}`: expected `key: value`, found `{line}`.",
+                    self.name
+                )));
+            };
+
+            let value = Some(value.trim().to_string());
+
+            match key.trim() {
+                "part_1" => self.part_1 = value,
+                "part_2" => self.part_2 = value,
+                key => {
+                    return Err(ExampleError::Parser(format!(
+                        "example `{}`: unknown key `{key}`, expecting `part_1` or `part_2`.",
+                        self.name
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn strip_line<'a>(s: &'a str, line: &str) -> Option<&'a str> {
+    let rest = s.strip_prefix(line)?;
+    rest.strip_prefix("\r\n")
+        .or_else(|| rest.strip_prefix('\n'))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Directory holding the named examples of a puzzle, e.g. `data/examples/07`.
+pub fn examples_dir(puzzle: impl Into<Puzzle>) -> PathBuf {
+    let puzzle = puzzle.into();
+    Path::new(&puzzle.data_dir("examples")).join(puzzle.day.to_string())
+}
+
+/// Read all examples of a puzzle, sorted by name.
+pub fn read_examples(puzzle: impl Into<Puzzle>) -> Result<Vec<Example>, ExampleError> {
+    read_examples_from(&examples_dir(puzzle))
+}
+
+fn read_examples_from(dir: &Path) -> Result<Vec<Example>, ExampleError> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<_, _>>()?;
+
+    paths.retain(|path| path.extension().is_some_and(|ext| ext == "txt"));
+    paths.sort_unstable();
+
+    let mut examples = vec![];
+
+    for path in paths {
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let content = fs::read_to_string(&path)?;
+        let sidecar = path.with_extension(SIDECAR_EXTENSION);
+
+        let example = if sidecar.exists() {
+            let mut example = Example {
+                name,
+                input: content,
+                part_1: None,
+                part_2: None,
+            };
+            example.set_expected(&fs::read_to_string(sidecar)?)?;
+            example
+        } else {
+            Example::parse(&name, &content)?
+        };
+
+        examples.push(example);
+    }
+
+    Ok(examples)
+}
+
+/// Run every example of a solution through all of its parts and compare the answers
+/// against the expected ones.
+///
+/// # Panics
+/// If the examples can't be read, there are none, or any answer differs from the expected one.
+/// All mismatches are listed in the panic message.
+pub fn check_examples(solution: &dyn Solution) {
+    let puzzle = solution.puzzle();
+    let dir = examples_dir(puzzle);
+
+    let examples = read_examples_from(&dir)
+        .unwrap_or_else(|e| panic!("could not read examples from {}: {e}", dir.display()));
+
+    assert!(!examples.is_empty(), "no examples in {}", dir.display());
+
+    let mismatches = check(solution, &examples);
+
+    assert!(
+        mismatches.is_empty(),
+        "{} example answer(s) of day {puzzle} differ:\n{}",
+        mismatches.len(),
+        mismatches.join("\n")
+    );
+}
+
+/// Returns a description of every answer that differs from the expected one.
+fn check(solution: &dyn Solution, examples: &[Example]) -> Vec<String> {
+    let mut mismatches = vec![];
+
+    for example in examples {
+        for &part in solution.parts() {
+            let Some(expected) = example.expected(part) else {
+                continue;
+            };
+
+            let actual = solution.solve(part, &example.input).to_strings();
+
+            if actual.as_ref().ok() != Some(&Some(expected.clone())) {
+                let actual = match actual {
+                    Ok(Some(x)) => format!("`{x}`"),
+                    Ok(None) => "no answer".into(),
+                    Err(e) => format!("error: {e}"),
+                };
+                mismatches.push(format!(
+                    "  {} part {part}: expected `{expected}`, got {actual}",
+                    example.name
+                ));
+            }
+        }
+    }
+
+    mismatches
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::{check, read_examples_from, Example};
+    use crate::{
+        puzzle,
+        template::{examples_dir, test_utils::temp_dir, Answer, Puzzle, Solution},
+    };
+
+    #[test]
+    fn parses_front_matter() {
+        let example = Example::parse("gap", "---\npart_1: 42\n---\nline 1\nline 2\n").unwrap();
+        assert_eq!(example.part_1, Some("42".into()));
+        assert_eq!(example.part_2, None);
+        assert_eq!(example.input, "line 1\nline 2\n");
+    }
+
+    #[test]
+    fn parses_examples_without_front_matter() {
+        let example = Example::parse("plain", "---- not front-matter\n").unwrap();
+        assert_eq!(example.input, "---- not front-matter\n");
+        assert_eq!(example.part_1, None);
+    }
+
+    #[test]
+    fn rejects_malformed_front_matter() {
+        assert!(Example::parse("open", "---\npart_1: 1\n").is_err());
+        assert!(Example::parse("typo", "---\npart1: 1\n---\n").is_err());
+    }
+
+    #[test]
+    fn reads_examples_with_sidecars() {
+        let dir = temp_dir("examples");
+        fs::write(dir.join("b.txt"), "---\npart_2: 2\n---\ninput b").unwrap();
+        fs::write(dir.join("a.txt"), "\u{feff}---\ninput a").unwrap();
+        fs::write(dir.join("a.expected"), "part_1: 1\n").unwrap();
+        fs::write(dir.join("notes.md"), "ignored").unwrap();
+
+        let examples = read_examples_from(&dir).unwrap();
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].name, "a");
+        assert_eq!(examples[0].input, "\u{feff}---\ninput a");
+        assert_eq!(examples[0].part_1, Some("1".into()));
+        assert_eq!(examples[1].input, "input b");
+        assert_eq!(examples[1].part_2, Some("2".into()));
+    }
+
+    struct LineCount;
+
+    impl Solution for LineCount {
+        fn puzzle(&self) -> Puzzle {
+            puzzle!(1)
+        }
+
+        fn parts(&self) -> &'static [u8] {
+            &[1, 2]
+        }
+
+        fn solve(&self, part: u8, input: &str) -> Answer {
+            match part {
+                1 => Answer::Solved(Box::new(input.lines().count())),
+                _ if input.is_empty() => Answer::Failed(Box::new("input is empty")),
+                _ => Answer::Unsolved,
+            }
+        }
+    }
+
+    #[test]
+    fn reports_mismatches() {
+        let examples = [
+            Example::parse("ok", "---\npart_1: 2\n---\na\nb\n").unwrap(),
+            Example::parse("wrong", "---\npart_1: 3\npart_2: 1\n---\na\n").unwrap(),
+            Example::parse("empty", "---\npart_2: 1\n---\n").unwrap(),
+        ];
+
+        let mismatches = check(&LineCount, &examples);
+        assert_eq!(
+            mismatches,
+            [
+                "  wrong part 1: expected `3`, got `1`",
+                "  wrong part 2: expected `1`, got no answer",
+                "  empty part 2: expected `1`, got error: input is empty",
+            ]
+        );
+    }
+
+    #[test]
+    fn locates_examples() {
+        assert_eq!(examples_dir(puzzle!(7)), PathBuf::from("data/examples/07"));
+        assert_eq!(
+            examples_dir(puzzle!(2026 / 7)),
+            PathBuf::from("data/2026/examples/07")
+        );
+    }
+}
@@ -0,0 +1,160 @@
+/// Puzzle inputs that are read on demand, for solutions that stream through inputs too large
+/// to hold in memory.
+///
+/// Streaming solutions are declared with `solution!(stream 2)` and take an `&Input` instead of
+/// an `&str`. An input is either a file, which is opened anew on every access, or text that is
+/// already in memory, e.g. an example. Both are read through the same methods:
+///
+/// ```
+/// # use i18n_puzzles::template::Input;
+/// let input = Input::Text("a\nb\n");
+/// let lines: Vec<_> = input.lines().unwrap().collect::<Result<_, _>>().unwrap();
+/// assert_eq!(lines, ["a", "b"]);
+/// ```
+use std::{
+    borrow::Cow,
+    env,
+    fs::{self, File},
+    io::{self, BufRead, BufReader},
+    ops::Deref,
+    path::PathBuf,
+};
+
+use memmap2::Mmap;
+
+use crate::template::Puzzle;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Input<'a> {
+    /// Text that is already in memory.
+    Text(&'a str),
+    /// A file that is read on demand.
+    File(PathBuf),
+}
+
+impl Input<'_> {
+    /// The input file of a puzzle, e.g. `data/inputs/07.txt`. Fails if the file doesn't exist.
+    pub fn open(folder: &str, puzzle: impl Into<Puzzle>) -> io::Result<Input<'static>> {
+        let path = env::current_dir()?.join(puzzle.into().data_path(folder, "txt"));
+        fs::metadata(&path)?;
+        Ok(Input::File(path))
+    }
+
+    /// A buffered reader over the input.
+    pub fn reader(&self) -> io::Result<Box<dyn BufRead + '_>> {
+        match self {
+            Input::Text(text) => Ok(Box::new(text.as_bytes())),
+            Input::File(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+        }
+    }
+
+    /// The lines of the input without their line endings, see [`str::lines`].
+    pub fn lines(&self) -> io::Result<Lines<'_>> {
+        match self {
+            Input::Text(text) => Ok(Lines::Text(text.lines())),
+            Input::File(path) => Ok(Lines::File(BufReader::new(File::open(path)?).lines())),
+        }
+    }
+
+    /// The raw bytes of the input. Files are memory-mapped rather than read.
+    pub fn bytes(&self) -> io::Result<Bytes<'_>> {
+        match self {
+            Input::Text(text) => Ok(Bytes::Text(text.as_bytes())),
+            Input::File(path) => {
+                let file = File::open(path)?;
+                // SAFETY: inputs are not expected to be modified while a solution runs.
+                let mmap = unsafe { Mmap::map(&file)? };
+                Ok(Bytes::Mapped(mmap))
+            }
+        }
+    }
+
+    /// Reads the whole input into memory.
+    pub fn read_to_string(&self) -> io::Result<Cow<'_, str>> {
+        match self {
+            Input::Text(text) => Ok(Cow::Borrowed(text)),
+            Input::File(path) => fs::read_to_string(path).map(Cow::Owned),
+        }
+    }
+}
+
+/// Iterator over the lines of an [`Input`].
+pub enum Lines<'a> {
+    Text(std::str::Lines<'a>),
+    File(io::Lines<BufReader<File>>),
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = io::Result<Cow<'a, str>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Lines::Text(lines) => lines.next().map(|line| Ok(Cow::Borrowed(line))),
+            Lines::File(lines) => Some(lines.next()?.map(|line| {
+                // `BufRead::lines` only strips `\n`, match `str::lines`.
+                let line = match line.strip_suffix('\r') {
+                    Some(stripped) => stripped.to_string(),
+                    None => line,
+                };
+                Cow::Owned(line)
+            })),
+        }
+    }
+}
+
+/// The bytes of an [`Input`], borrowed or memory-mapped.
+pub enum Bytes<'a> {
+    Text(&'a [u8]),
+    Mapped(Mmap),
+}
+
+impl Deref for Bytes<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Bytes::Text(bytes) => bytes,
+            Bytes::Mapped(mmap) => mmap,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs};
+
+    use super::Input;
+
+    #[test]
+    fn reads_files_and_text_alike() {
+        let path = env::temp_dir().join(format!("i18n-puzzles-input-{}.txt", std::process::id()));
+        fs::write(&path, "2019-06-05\r\nÆ\n\nlast").unwrap();
+
+        let file = Input::File(path.clone());
+        let text = Input::Text("2019-06-05\r\nÆ\n\nlast");
+
+        for input in [file, text] {
+            let lines: Vec<_> = input.lines().unwrap().collect::<Result<_, _>>().unwrap();
+            assert_eq!(lines, ["2019-06-05", "Æ", "", "last"]);
+
+            assert_eq!(&input.bytes().unwrap()[..4], b"2019");
+            assert_eq!(input.read_to_string().unwrap().len(), 20);
+
+            let mut first = String::new();
+            input.reader().unwrap().read_line(&mut first).unwrap();
+            assert_eq!(first, "2019-06-05\r\n");
+        }
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn maps_empty_files() {
+        let path = env::temp_dir().join(format!("i18n-puzzles-empty-{}.txt", std::process::id()));
+        fs::write(&path, "").unwrap();
+        assert!(Input::File(path.clone()).bytes().unwrap().is_empty());
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -1,14 +1,28 @@
-use std::{env, fs};
+use std::{env, fs, io};
 
-// pub mod aoc_cli;
 pub mod commands;
+pub mod compare;
+pub mod puzzle_source;
+pub mod report;
 pub mod runner;
 
 pub use day::*;
+pub use examples::*;
+pub use input::*;
+pub use puzzle::*;
+pub use solution::*;
 
+mod answers;
 mod day;
+mod examples;
+mod input;
+mod puzzle;
 mod readme_benchmarks;
+mod registry;
 mod run_multi;
+mod solution;
+#[cfg(feature = "test_lib")]
+pub mod test_utils;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
@@ -17,52 +31,158 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
+pub fn read_file(folder: &str, puzzle: impl Into<Puzzle>) -> String {
+    let f = try_read_file(folder, puzzle);
     f.expect("could not open input file")
 }
 
+/// Helper function that reads a text file to a string, returning an error if it can't be read.
+pub fn try_read_file(folder: &str, puzzle: impl Into<Puzzle>) -> io::Result<String> {
+    let cwd = env::current_dir()?;
+    let filepath = cwd.join(puzzle.into().data_path(folder, "txt"));
+    fs::read_to_string(filepath)
+}
+
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: impl Into<Puzzle>, part: u8) -> String {
+    let puzzle = puzzle.into();
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(puzzle.data_dir(folder))
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `PUZZLE` and `DAY` and sets up the input and runner for each part.
+///
+/// The first parameter is either a day of the default edition (`solution!(7)`) or a day of
+/// another edition (`solution!(2026 / 7)`). The optional, second parameter (1 or 2) allows you
+/// to only run a single part of the solution.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Parts return either `Option<T>` or `Result<T, E>`, see [`PartResult`].
+///
+/// Parts take the whole input as `&str`. Prefix the day with `stream` (`solution!(stream 7)`)
+/// to have them take an [`Input`] instead, which reads the input file line by line or
+/// memory-maps it on demand rather than holding all of it in memory.
+///
+/// Additionally, this registers the static `SOLUTION`, which the runner binary uses to execute
+/// the solution in-process (see `src/solutions.rs`).
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    (stream $($args:tt)*) => {
+        $crate::solution!(@parts stream, $($args)*);
+    };
+
+    (@parts $mode:ident, $edition:literal / $day:literal) => {
+        $crate::solution!(@impl $mode, $crate::puzzle!($edition / $day), [part_one, 1] [part_two, 2]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    (@parts $mode:ident, $edition:literal / $day:literal, 1) => {
+        $crate::solution!(@impl $mode, $crate::puzzle!($edition / $day), [part_one, 1]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    (@parts $mode:ident, $edition:literal / $day:literal, 2) => {
+        $crate::solution!(@impl $mode, $crate::puzzle!($edition / $day), [part_two, 2]);
     };
+    (@parts $mode:ident, $day:expr) => {
+        $crate::solution!(@impl $mode, $crate::puzzle!($day), [part_one, 1] [part_two, 2]);
+    };
+    (@parts $mode:ident, $day:expr, 1) => {
+        $crate::solution!(@impl $mode, $crate::puzzle!($day), [part_one, 1]);
+    };
+    (@parts $mode:ident, $day:expr, 2) => {
+        $crate::solution!(@impl $mode, $crate::puzzle!($day), [part_two, 2]);
+    };
+
+    (@impl $mode:ident, $puzzle:expr, $( [$func:expr, $part:expr] )*) => {
+        /// The current puzzle.
+        const PUZZLE: $crate::template::Puzzle = $puzzle;
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
         /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        #[allow(dead_code)]
+        const DAY: $crate::template::Day = PUZZLE.day;
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        /// The solution as registered with the runner binary.
+        pub static SOLUTION: Solver = Solver;
+
+        pub struct Solver;
+
+        $crate::solution!(@solver $mode, $( [$func, $part] )*);
+    };
+
+    (@solver text, $( [$func:expr, $part:expr] )*) => {
+        impl $crate::template::Solution for Solver {
+            fn puzzle(&self) -> $crate::template::Puzzle {
+                PUZZLE
+            }
+
+            fn parts(&self) -> &'static [u8] {
+                &[$( $part ),*]
+            }
+
+            fn solve(&self, part: u8, input: &str) -> $crate::template::Answer {
+                $(
+                    if part == $part {
+                        return $crate::template::PartResult::into_answer(($func)(input));
+                    }
+                )*
+                $crate::template::Answer::Unsolved
+            }
+        }
+
+        // NOTE: unused when the solution is compiled into the runner binary.
+        #[allow(dead_code)]
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let input = $crate::template::read_file("inputs", PUZZLE);
+            let reports = [$( run_part($func, &input, PUZZLE, $part) ),*];
+            $crate::template::report::print_reports(&reports, $crate::template::report::Format::from_args());
         }
     };
+
+    (@solver stream, $( [$func:expr, $part:expr] )*) => {
+        impl $crate::template::Solution for Solver {
+            fn puzzle(&self) -> $crate::template::Puzzle {
+                PUZZLE
+            }
+
+            fn parts(&self) -> &'static [u8] {
+                &[$( $part ),*]
+            }
+
+            fn solve(&self, part: u8, input: &str) -> $crate::template::Answer {
+                self.solve_input(part, &$crate::template::Input::Text(input))
+            }
+
+            fn is_streaming(&self) -> bool {
+                true
+            }
+
+            fn solve_input(&self, part: u8, input: &$crate::template::Input) -> $crate::template::Answer {
+                $(
+                    if part == $part {
+                        return $crate::template::PartResult::into_answer(($func)(input));
+                    }
+                )*
+                $crate::template::Answer::Unsolved
+            }
+        }
+
+        // NOTE: unused when the solution is compiled into the runner binary.
+        #[allow(dead_code)]
+        fn main() {
+            use $crate::template::runner::*;
+            let input = $crate::template::Input::open("inputs", PUZZLE).expect("could not open input file");
+            let reports = [$( run_part($func, &input, PUZZLE, $part) ),*];
+            $crate::template::report::print_reports(&reports, $crate::template::report::Format::from_args());
+        }
+    };
+
+    ($($args:tt)*) => {
+        $crate::solution!(@parts text, $($args)*);
+    };
 }
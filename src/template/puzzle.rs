@@ -0,0 +1,284 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::Day;
+
+/// The edition all puzzles belong to unless stated otherwise.
+///
+/// Puzzles of this edition keep the flat layout of a single-edition workspace (`data/inputs/07.txt`,
+/// `src/bin/07.rs`), puzzles of other editions live in their own directories (`data/2026/inputs/07.txt`,
+/// `src/bin/2026-07.rs`).
+pub const DEFAULT_EDITION: Edition = Edition(2025);
+
+/// An edition (i.e. the year) of the puzzles, a four digit number.
+///
+/// ```
+/// # use i18n_puzzles::template::Edition;
+/// let edition = Edition::new(2025).unwrap();
+/// assert_eq!(edition.to_string(), "2025")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Edition(u16);
+
+impl Edition {
+    /// Creates an [`Edition`] from the provided year if it has four digits,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if !(1000..=9999).contains(&year) {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Edition`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+}
+
+impl Display for Edition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Edition {
+    type Err = EditionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| EditionFromStrError)?;
+        Self::new(year).ok_or(EditionFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing an [`Edition`].
+#[derive(Debug)]
+pub struct EditionFromStrError;
+
+impl Error for EditionFromStrError {}
+
+impl Display for EditionFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a four digit year")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Identifies a puzzle by its edition and day.
+///
+/// # Display
+/// This value displays as `<edition>/<day>`. When parsing, the edition may be omitted
+/// for puzzles of the [`DEFAULT_EDITION`].
+///
+/// ```
+/// # use i18n_puzzles::template::Puzzle;
+/// let puzzle: Puzzle = "2025/07".parse().unwrap();
+/// assert_eq!(puzzle, "7".parse().unwrap());
+/// assert_eq!(puzzle.to_string(), "2025/07")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    pub edition: Edition,
+    pub day: Day,
+}
+
+impl Puzzle {
+    pub const fn new(edition: Edition, day: Day) -> Self {
+        Self { edition, day }
+    }
+
+    pub fn is_default_edition(self) -> bool {
+        self.edition == DEFAULT_EDITION
+    }
+
+    /// Directory holding the files of this puzzle's edition relative to the `data` directory, e.g. `2026/inputs`.
+    pub fn relative_data_dir(self, folder: &str) -> String {
+        if self.is_default_edition() {
+            folder.to_string()
+        } else {
+            format!("{}/{folder}", self.edition)
+        }
+    }
+
+    /// Directory holding the files of this puzzle's edition, e.g. `data/2026/inputs`.
+    pub fn data_dir(self, folder: &str) -> String {
+        format!("data/{}", self.relative_data_dir(folder))
+    }
+
+    /// Path of a file of this puzzle relative to the `data` directory, e.g. `2026/inputs/07.txt`.
+    pub fn relative_data_path(self, folder: &str, extension: &str) -> String {
+        format!(
+            "{}/{}.{extension}",
+            self.relative_data_dir(folder),
+            self.day
+        )
+    }
+
+    /// Path of a file of this puzzle in this repository, e.g. `data/2026/inputs/07.txt`.
+    pub fn data_path(self, folder: &str, extension: &str) -> String {
+        format!("data/{}", self.relative_data_path(folder, extension))
+    }
+
+    /// Name of the solution binary, e.g. `07` or `2026-07`.
+    pub fn bin_name(self) -> String {
+        if self.is_default_edition() {
+            self.day.to_string()
+        } else {
+            format!("{}-{}", self.edition, self.day)
+        }
+    }
+
+    /// Path of the solution binary's source file, e.g. `./src/bin/2026-07.rs`.
+    pub fn bin_path(self) -> String {
+        format!("./src/bin/{}.rs", self.bin_name())
+    }
+
+    /// Parses the name of a solution binary, the inverse of [`Puzzle::bin_name`].
+    pub fn from_bin_name(name: &str) -> Option<Self> {
+        match name.split_once('-') {
+            Some((edition, day)) if day.len() == 2 => {
+                let puzzle = Self::new(edition.parse().ok()?, day.parse().ok()?);
+                (!puzzle.is_default_edition()).then_some(puzzle)
+            }
+            None if name.len() == 2 => Some(Self::from(name.parse::<Day>().ok()?)),
+            _ => None,
+        }
+    }
+}
+
+impl From<Day> for Puzzle {
+    fn from(day: Day) -> Self {
+        Self::new(DEFAULT_EDITION, day)
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.edition, self.day)
+    }
+}
+
+impl FromStr for Puzzle {
+    type Err = PuzzleFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(['/', '-']) {
+            Some((edition, day)) => Ok(Self::new(
+                edition.parse().map_err(|_| PuzzleFromStrError)?,
+                day.parse().map_err(|_| PuzzleFromStrError)?,
+            )),
+            None => Ok(Self::from(
+                s.parse::<Day>().map_err(|_| PuzzleFromStrError)?,
+            )),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Puzzle`].
+#[derive(Debug)]
+pub struct PuzzleFromStrError;
+
+impl Error for PuzzleFromStrError {}
+
+impl Display for PuzzleFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a day number between 1 and 25, optionally prefixed by an edition (e.g. `2025/07`)")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An iterator that yields every puzzle of an edition.
+pub fn all_puzzles(edition: Edition) -> impl Iterator<Item = Puzzle> {
+    crate::template::all_days().map(move |day| Puzzle::new(edition, day))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Puzzle`] value in a const context, either `puzzle!(7)` for the default edition or `puzzle!(2026 / 7)`.
+#[macro_export]
+macro_rules! puzzle {
+    ($edition:literal / $day:literal) => {{
+        const _ASSERT: () = assert!(
+            $edition >= 1000 && $edition <= 9999,
+            concat!(
+                "invalid edition `",
+                $edition,
+                "`, expecting a four digit year"
+            ),
+        );
+        $crate::template::Puzzle::new(
+            $crate::template::Edition::__new_unchecked($edition),
+            $crate::day!($day),
+        )
+    }};
+    ($day:expr) => {
+        $crate::template::Puzzle::new($crate::template::DEFAULT_EDITION, $crate::day!($day))
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Puzzle, DEFAULT_EDITION};
+    use crate::{day, puzzle};
+
+    #[test]
+    fn parses_puzzles() {
+        assert_eq!("7".parse::<Puzzle>().unwrap(), Puzzle::from(day!(7)));
+        assert_eq!("2025/07".parse::<Puzzle>().unwrap(), puzzle!(7));
+        assert_eq!("2026/07".parse::<Puzzle>().unwrap(), puzzle!(2026 / 7));
+        assert_eq!("2026-07".parse::<Puzzle>().unwrap(), puzzle!(2026 / 7));
+        assert!("26/07".parse::<Puzzle>().is_err());
+        assert!("2026/26".parse::<Puzzle>().is_err());
+        assert!("foo".parse::<Puzzle>().is_err());
+    }
+
+    #[test]
+    fn displays_puzzles() {
+        assert_eq!(puzzle!(7).to_string(), format!("{DEFAULT_EDITION}/07"));
+        assert_eq!(puzzle!(2026 / 12).to_string(), "2026/12");
+    }
+
+    #[test]
+    fn orders_by_edition() {
+        let mut puzzles = vec![puzzle!(2026 / 1), puzzle!(2024 / 20), puzzle!(2025 / 3)];
+        puzzles.sort_unstable();
+        assert_eq!(
+            puzzles,
+            [puzzle!(2024 / 20), puzzle!(2025 / 3), puzzle!(2026 / 1)]
+        );
+    }
+
+    #[test]
+    fn builds_paths() {
+        assert_eq!(puzzle!(7).data_path("inputs", "txt"), "data/inputs/07.txt");
+        assert_eq!(puzzle!(7).bin_path(), "./src/bin/07.rs");
+        assert_eq!(
+            puzzle!(2026 / 7).data_path("inputs", "txt"),
+            "data/2026/inputs/07.txt"
+        );
+        assert_eq!(puzzle!(2026 / 7).bin_path(), "./src/bin/2026-07.rs");
+    }
+
+    #[test]
+    fn parses_bin_names() {
+        for puzzle in [puzzle!(7), puzzle!(2026 / 7)] {
+            assert_eq!(Puzzle::from_bin_name(&puzzle.bin_name()), Some(puzzle));
+        }
+
+        let default = format!("{DEFAULT_EDITION}-07");
+        assert_eq!(Puzzle::from_bin_name(&default), None);
+        assert_eq!(Puzzle::from_bin_name("7"), None);
+        assert_eq!(Puzzle::from_bin_name("i18n_puzzles"), None);
+    }
+}
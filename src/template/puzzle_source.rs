@@ -0,0 +1,437 @@
+/// Fetches puzzle descriptions and inputs from a configurable source.
+///
+/// A source is either a local directory or an HTTP(S) mirror that follows the layout of the `data`
+/// directory, i.e. `<source>/puzzles/NN.md` and `<source>/inputs/NN.txt`, with the files of editions
+/// other than the default one in `<source>/<edition>/…`.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use crate::template::Puzzle;
+
+/// Environment variable holding the directory or base URL to fetch from.
+pub const SOURCE_ENV: &str = "I18N_PUZZLES_SOURCE";
+/// Environment variable holding the session cookie sent to HTTP sources.
+pub const SESSION_ENV: &str = "I18N_PUZZLES_SESSION";
+/// File in the home directory holding the session cookie, if the environment variable is not set.
+pub const SESSION_FILE: &str = ".i18n-puzzles.session";
+
+static CACHE_DIR: &str = "./data/.cache";
+
+/// The kinds of files that can be fetched for a day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Resource {
+    Puzzle,
+    Input,
+}
+
+impl Resource {
+    /// Path of the resource relative to the `data` directory or a source.
+    pub fn relative_path(self, puzzle: Puzzle) -> String {
+        match self {
+            Resource::Puzzle => puzzle.relative_data_path("puzzles", "md"),
+            Resource::Input => puzzle.relative_data_path("inputs", "txt"),
+        }
+    }
+
+    /// Path the resource is written to in this repository.
+    pub fn local_path(self, puzzle: Puzzle) -> String {
+        format!("data/{}", self.relative_path(puzzle))
+    }
+}
+
+#[derive(Debug)]
+pub enum SourceError {
+    NotConfigured,
+    NotFound(String),
+    Unauthorized(String),
+    BadStatus(String, u16),
+    NotUtf8(String),
+    CommandNotCallable,
+    IO(io::Error),
+}
+
+impl Display for SourceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SourceError::NotConfigured => {
+                write!(f, "no puzzle source configured, set `{SOURCE_ENV}`.")
+            }
+            SourceError::NotFound(location) => write!(f, "{location} does not exist."),
+            SourceError::Unauthorized(location) => {
+                write!(
+                    f,
+                    "not authorized to fetch {location}, check your session cookie."
+                )
+            }
+            SourceError::BadStatus(location, status) => {
+                write!(f, "fetching {location} failed with status {status}.")
+            }
+            SourceError::NotUtf8(location) => write!(f, "{location} is not valid UTF-8."),
+            SourceError::CommandNotCallable => write!(f, "curl could not be called."),
+            SourceError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for SourceError {
+    fn from(e: io::Error) -> Self {
+        SourceError::IO(e)
+    }
+}
+
+/// A place puzzle descriptions and inputs can be fetched from.
+pub trait PuzzleSource {
+    fn fetch(&self, resource: Resource, puzzle: Puzzle) -> Result<String, SourceError>;
+
+    /// Like `fetch`, but bypasses any copy kept by the source, e.g. to replace a bad fetch.
+    fn fetch_fresh(&self, resource: Resource, puzzle: Puzzle) -> Result<String, SourceError> {
+        self.fetch(resource, puzzle)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Reads files from a local directory, e.g. a checked out mirror.
+pub struct DirectorySource {
+    root: PathBuf,
+}
+
+impl DirectorySource {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl PuzzleSource for DirectorySource {
+    fn fetch(&self, resource: Resource, puzzle: Puzzle) -> Result<String, SourceError> {
+        let path = self.root.join(resource.relative_path(puzzle));
+
+        fs::read_to_string(&path).map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => SourceError::NotFound(path.display().to_string()),
+            _ => SourceError::IO(e),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Fetches files from an HTTP(S) mirror by calling `curl`.
+pub struct HttpSource {
+    base_url: String,
+    session: Option<String>,
+}
+
+impl HttpSource {
+    pub fn new(base_url: &str, session: Option<String>) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+        }
+    }
+
+    fn cookie(&self) -> Option<String> {
+        let session = self.session.as_ref()?;
+
+        // allow passing complete cookies like `name=value`.
+        if session.contains('=') {
+            Some(session.clone())
+        } else {
+            Some(format!("session={session}"))
+        }
+    }
+}
+
+impl PuzzleSource for HttpSource {
+    fn fetch(&self, resource: Resource, puzzle: Puzzle) -> Result<String, SourceError> {
+        let url = format!("{}/{}", self.base_url, resource.relative_path(puzzle));
+
+        let mut args = vec![
+            "--silent".to_string(),
+            "--location".to_string(),
+            "--write-out".to_string(),
+            "\n%{http_code}".to_string(),
+        ];
+
+        // The cookie is passed as a config file on stdin, arguments are visible to other users
+        args.push("--config".into());
+        args.push("-".into());
+        args.push(url.clone());
+
+        let mut child = Command::new("curl")
+            .args(&args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|_| SourceError::CommandNotCallable)?;
+
+        let mut config = String::new();
+        if let Some(cookie) = self.cookie() {
+            let cookie = cookie.replace('\\', "\\\\").replace('"', "\\\"");
+            config.push_str(&format!("cookie = \"{cookie}\"\n"));
+        }
+
+        // NOTE: the handle is dropped after writing, which closes stdin
+        child.stdin.take().unwrap().write_all(config.as_bytes())?;
+        let output = child.wait_with_output()?;
+
+        // NOTE: the status is split off the raw bytes, so that the body is never decoded lossily
+        let mut body = output.stdout;
+        let (len, status) = body
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .and_then(|i| {
+                let status = std::str::from_utf8(&body[i + 1..]).ok()?;
+                Some((i, status.parse::<u16>().ok()?))
+            })
+            .ok_or_else(|| SourceError::BadStatus(url.clone(), 0))?;
+        body.truncate(len);
+
+        match status {
+            200..=299 => String::from_utf8(body).map_err(|_| SourceError::NotUtf8(url)),
+            401 | 403 => Err(SourceError::Unauthorized(url)),
+            404 => Err(SourceError::NotFound(url)),
+            status => Err(SourceError::BadStatus(url, status)),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Wraps another source and keeps a copy of everything it fetched, so files are fetched at most once.
+pub struct CachedSource<S: PuzzleSource> {
+    inner: S,
+    cache_dir: PathBuf,
+}
+
+impl<S: PuzzleSource> CachedSource<S> {
+    pub fn new(inner: S, cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            cache_dir: cache_dir.into(),
+        }
+    }
+
+    fn cache_path(&self, resource: Resource, puzzle: Puzzle) -> PathBuf {
+        self.cache_dir.join(resource.relative_path(puzzle))
+    }
+}
+
+impl<S: PuzzleSource> PuzzleSource for CachedSource<S> {
+    fn fetch(&self, resource: Resource, puzzle: Puzzle) -> Result<String, SourceError> {
+        if let Ok(cached) = fs::read_to_string(self.cache_path(resource, puzzle)) {
+            return Ok(cached);
+        }
+
+        self.fetch_fresh(resource, puzzle)
+    }
+
+    /// Fetches from the wrapped source and replaces the cached copy.
+    fn fetch_fresh(&self, resource: Resource, puzzle: Puzzle) -> Result<String, SourceError> {
+        let path = self.cache_path(resource, puzzle);
+        let content = self.inner.fetch_fresh(resource, puzzle)?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, &content)?;
+
+        Ok(content)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Reads the session cookie from the environment or from `~/.i18n-puzzles.session`.
+pub fn read_session() -> Option<String> {
+    if let Ok(session) = env::var(SESSION_ENV) {
+        return Some(session.trim().to_string());
+    }
+
+    let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+    let session = fs::read_to_string(Path::new(&home).join(SESSION_FILE)).ok()?;
+    Some(session.trim().to_string())
+}
+
+/// Builds the source configured via `I18N_PUZZLES_SOURCE`.
+pub fn from_env() -> Result<Box<dyn PuzzleSource>, SourceError> {
+    let source = env::var(SOURCE_ENV).map_err(|_| SourceError::NotConfigured)?;
+
+    if source.starts_with("http://") || source.starts_with("https://") {
+        let http = HttpSource::new(&source, read_session());
+        Ok(Box::new(CachedSource::new(http, CACHE_DIR)))
+    } else {
+        Ok(Box::new(DirectorySource::new(source)))
+    }
+}
+
+/// Fetch a resource and write it to its local path.
+/// Existing files are only replaced if they are empty (e.g. created by `scaffold`) or `overwrite` is set.
+/// With `overwrite`, the resource is fetched fresh rather than from a cache.
+pub fn download(
+    source: &dyn PuzzleSource,
+    resource: Resource,
+    puzzle: Puzzle,
+    overwrite: bool,
+) -> Result<bool, SourceError> {
+    let path = resource.local_path(puzzle);
+
+    let is_empty = fs::metadata(&path).map_or(true, |m| m.len() == 0);
+    if !is_empty && !overwrite {
+        return Ok(false);
+    }
+
+    let content = if overwrite {
+        source.fetch_fresh(resource, puzzle)?
+    } else {
+        source.fetch(resource, puzzle)?
+    };
+
+    if let Some(parent) = Path::new(&path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, content)?;
+
+    Ok(true)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        cell::Cell,
+        fs,
+        io::{Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::{CachedSource, DirectorySource, HttpSource, PuzzleSource, Resource, SourceError};
+    use crate::{
+        puzzle,
+        template::{test_utils::temp_dir, Puzzle},
+    };
+
+    /// Serves a single request, responding with `status` and `body`. Returns the received request.
+    fn serve_once(
+        status: &'static str,
+        body: &'static [u8],
+    ) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buffer = [0; 4096];
+            let n = stream.read(&mut buffer).unwrap();
+
+            let response = format!(
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            stream.write_all(body).unwrap();
+
+            String::from_utf8_lossy(&buffer[..n]).to_string()
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn reads_from_directory() {
+        let dir = temp_dir("directory");
+        fs::create_dir_all(dir.join("inputs")).unwrap();
+        fs::write(dir.join("inputs/03.txt"), "input").unwrap();
+
+        let source = DirectorySource::new(&dir);
+        assert_eq!(source.fetch(Resource::Input, puzzle!(3)).unwrap(), "input");
+        assert!(matches!(
+            source.fetch(Resource::Puzzle, puzzle!(3)),
+            Err(SourceError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn fetches_from_http_mirror() {
+        let (url, server) = serve_once("200 OK", b"# Puzzle\n");
+        let source = HttpSource::new(&url, Some("secret".into()));
+
+        assert_eq!(
+            source.fetch(Resource::Puzzle, puzzle!(2026 / 7)).unwrap(),
+            "# Puzzle\n"
+        );
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2026/puzzles/07.md "));
+        assert!(request.contains("Cookie: session=secret"));
+    }
+
+    #[test]
+    fn reports_http_errors() {
+        let (url, server) = serve_once("403 Forbidden", b"");
+        let source = HttpSource::new(&url, None);
+
+        assert!(matches!(
+            source.fetch(Resource::Input, puzzle!(1)),
+            Err(SourceError::Unauthorized(_))
+        ));
+        server.join().unwrap();
+
+        // `größe` in Latin-1
+        let (url, server) = serve_once("200 OK", b"gr\xf6\xdfe\n");
+        let source = HttpSource::new(&url, None);
+
+        assert!(matches!(
+            source.fetch(Resource::Input, puzzle!(1)),
+            Err(SourceError::NotUtf8(_))
+        ));
+        server.join().unwrap();
+    }
+
+    struct CountingSource(Cell<u32>);
+
+    impl PuzzleSource for CountingSource {
+        fn fetch(&self, _resource: Resource, puzzle: Puzzle) -> Result<String, SourceError> {
+            self.0.set(self.0.get() + 1);
+            Ok(format!("day {}", puzzle.day))
+        }
+    }
+
+    #[test]
+    fn caches_fetched_files() {
+        let dir = temp_dir("cache");
+        let source = CachedSource::new(CountingSource(Cell::new(0)), &dir);
+
+        assert_eq!(source.fetch(Resource::Input, puzzle!(2)).unwrap(), "day 02");
+        assert_eq!(source.fetch(Resource::Input, puzzle!(2)).unwrap(), "day 02");
+        assert_eq!(source.inner.0.get(), 1);
+        assert!(dir.join("inputs/02.txt").exists());
+    }
+
+    #[test]
+    fn refreshes_cached_files() {
+        let dir = temp_dir("refresh");
+        fs::create_dir_all(dir.join("inputs")).unwrap();
+        fs::write(dir.join("inputs/02.txt"), "partial").unwrap();
+
+        let source = CachedSource::new(CountingSource(Cell::new(0)), &dir);
+        assert_eq!(
+            source.fetch(Resource::Input, puzzle!(2)).unwrap(),
+            "partial"
+        );
+        assert_eq!(
+            source.fetch_fresh(Resource::Input, puzzle!(2)).unwrap(),
+            "day 02"
+        );
+        assert_eq!(source.fetch(Resource::Input, puzzle!(2)).unwrap(), "day 02");
+        assert_eq!(source.inner.0.get(), 1);
+    }
+}
@@ -2,8 +2,8 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use crate::template::report::Stats;
 use crate::template::timings::Timings;
-use crate::template::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -25,11 +25,6 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     let matches: Vec<_> = readme.match_indices(MARKER).collect();
 
@@ -52,6 +47,18 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
+fn format_cell(part: Option<String>, stats: Option<Stats>) -> String {
+    let part = part.unwrap_or_else(|| "-".into());
+
+    match stats {
+        Some(stats) => format!(
+            "`{part}` ± {:.1?} (median {:.1?}, p95 {:.1?})",
+            stats.std_dev, stats.median, stats.p95
+        ),
+        None => format!("`{part}`"),
+    }
+}
+
 fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
 
@@ -64,13 +71,21 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
     ];
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let puzzle = timing.puzzle;
+
+        // NOTE: only puzzles of other editions are labelled with theirs.
+        let label = if puzzle.is_default_edition() {
+            format!("Day {}", puzzle.day.into_inner())
+        } else {
+            format!("{} Day {}", puzzle.edition, puzzle.day.into_inner())
+        };
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            "| [{}]({}) | {} | {} |",
+            label,
+            puzzle.bin_path(),
+            format_cell(timing.part_1, timing.part_1_stats),
+            format_cell(timing.part_2, timing.part_2_stats)
         ));
     }
 
@@ -100,27 +115,36 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        puzzle, template::report::Stats, template::timings::Timing, template::timings::Timings,
+    };
+    use std::time::Duration;
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
+                    puzzle: puzzle!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
-                    day: day!(2),
+                    puzzle: puzzle!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
-                    day: day!(4),
+                    puzzle: puzzle!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 9e+10,
                 },
             ],
@@ -157,6 +181,22 @@ mod tests {
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
 
+    #[test]
+    fn format_benchmarks_with_stats() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_stats = Some(Stats::from_samples(vec![
+            Duration::from_millis(9),
+            Duration::from_millis(10),
+            Duration::from_millis(11),
+        ]));
+
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `10ms` ± 816.5µs (median 10.0ms, p95 11.0ms) | `20ms` |"
+        ));
+    }
+
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
@@ -180,4 +220,13 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_editions() {
+        let mut timings = get_mock_timings();
+        timings.data[2].puzzle = puzzle!(2026 / 4);
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("| [2026 Day 4](./src/bin/2026-04.rs) | `40ms` | `50ms` |"));
+    }
 }
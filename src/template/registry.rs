@@ -0,0 +1,91 @@
+/// Module that keeps the solution registry of the runner binary in sync with the scaffolded days.
+/// The registry includes every solution binary in `src/bin` as a module, so `all` and `time` can run them in-process.
+use std::{fs, io};
+
+use crate::template::Puzzle;
+
+static REGISTRY_FILE_PATH: &str = "./src/solutions.rs";
+static BIN_DIR_PATH: &str = "./src/bin";
+
+fn module_name(puzzle: Puzzle) -> String {
+    format!("day_{}", puzzle.bin_name().replace('-', "_"))
+}
+
+fn construct_registry(puzzles: &[Puzzle]) -> String {
+    let mut lines: Vec<String> = vec![
+        "//! Registry of all scaffolded solutions, compiled into the runner binary.".into(),
+        "//! This file is generated by `cargo scaffold`, do not edit it by hand.".into(),
+        "use i18n_puzzles::template::Solution;".into(),
+        String::new(),
+    ];
+
+    for puzzle in puzzles {
+        lines.push(format!("#[path = \"bin/{}.rs\"]", puzzle.bin_name()));
+        lines.push(format!("mod {};", module_name(*puzzle)));
+    }
+
+    lines.push(String::new());
+    lines.push("pub static SOLUTIONS: &[&dyn Solution] = &[".into());
+
+    for puzzle in puzzles {
+        lines.push(format!("    &{}::SOLUTION,", module_name(*puzzle)));
+    }
+
+    lines.push("];".into());
+    lines.push(String::new());
+
+    lines.join("\n")
+}
+
+/// Regenerate the registry from the solution files that exist on disk.
+pub fn update() -> Result<(), io::Error> {
+    let mut puzzles: Vec<Puzzle> = vec![];
+
+    for entry in fs::read_dir(BIN_DIR_PATH)? {
+        let path = entry?.path();
+
+        if path.extension().is_some_and(|ext| ext == "rs")
+            && let Some(puzzle) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(Puzzle::from_bin_name)
+        {
+            puzzles.push(puzzle);
+        }
+    }
+
+    puzzles.sort_unstable();
+    fs::write(REGISTRY_FILE_PATH, construct_registry(&puzzles))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::construct_registry;
+    use crate::puzzle;
+
+    #[test]
+    fn constructs_registry() {
+        let registry = construct_registry(&[puzzle!(1), puzzle!(12), puzzle!(2026 / 3)]);
+        let expected = [
+            "//! Registry of all scaffolded solutions, compiled into the runner binary.",
+            "//! This file is generated by `cargo scaffold`, do not edit it by hand.",
+            "use i18n_puzzles::template::Solution;",
+            "",
+            "#[path = \"bin/01.rs\"]",
+            "mod day_01;",
+            "#[path = \"bin/12.rs\"]",
+            "mod day_12;",
+            "#[path = \"bin/2026-03.rs\"]",
+            "mod day_2026_03;",
+            "",
+            "pub static SOLUTIONS: &[&dyn Solution] = &[",
+            "    &day_01::SOLUTION,",
+            "    &day_12::SOLUTION,",
+            "    &day_2026_03::SOLUTION,",
+            "];",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(registry, expected);
+    }
+}
@@ -0,0 +1,379 @@
+/// Machine-readable reports of solution runs, for scripts that should not depend on the human-readable output.
+use std::{collections::HashMap, error::Error, fmt::Display, str::FromStr, time::Duration};
+
+use tinyjson::JsonValue;
+
+use crate::template::Puzzle;
+
+/// The format results are printed in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Decorated output meant to be read by humans.
+    #[default]
+    Human,
+    /// A single JSON array of all part reports, printed once every part has run.
+    Json,
+    /// One JSON object per line, printed as soon as a part has run.
+    Ndjson,
+}
+
+impl Format {
+    /// Returns the format passed with `--format`, falling back to [`Format::Human`].
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+
+        args.iter()
+            .position(|x| x == "--format")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|format| format.parse().ok())
+            .unwrap_or_default()
+    }
+
+    pub fn is_human(self) -> bool {
+        self == Format::Human
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Human => f.write_str("human"),
+            Format::Json => f.write_str("json"),
+            Format::Ndjson => f.write_str("ndjson"),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = FormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Format::Human),
+            "json" => Ok(Format::Json),
+            "ndjson" => Ok(Format::Ndjson),
+            _ => Err(FormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Format`].
+#[derive(Debug)]
+pub struct FormatFromStrError;
+
+impl Error for FormatFromStrError {}
+
+impl Display for FormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `human`, `json` or `ndjson`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Execution time statistics of a solution part.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub average: Duration,
+    /// Number of measured iterations, including rejected outliers.
+    pub samples: u128,
+    pub min: Duration,
+    pub max: Duration,
+    pub median: Duration,
+    pub p5: Duration,
+    pub p95: Duration,
+    pub std_dev: Duration,
+    /// Number of samples rejected as outliers, which are excluded from all other statistics.
+    pub outliers: u128,
+}
+
+/// Samples further than this many (scaled) median absolute deviations from the median are rejected.
+const OUTLIER_THRESHOLD: f64 = 3.0;
+
+/// Scales the MAD to be a consistent estimator of the standard deviation of normally distributed samples.
+const MAD_SCALE: f64 = 1.4826;
+
+impl Stats {
+    /// Statistics for a part that was executed exactly once.
+    pub fn single(duration: Duration) -> Self {
+        Stats {
+            average: duration,
+            samples: 1,
+            min: duration,
+            max: duration,
+            median: duration,
+            p5: duration,
+            p95: duration,
+            std_dev: Duration::ZERO,
+            outliers: 0,
+        }
+    }
+
+    /// Compute statistics for a set of benchmark samples, rejecting outliers by their median absolute deviation.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "expected at least one sample");
+
+        samples.sort_unstable();
+        let total = samples.len();
+
+        let median = median_duration(&samples);
+
+        let mut deviations: Vec<Duration> = samples.iter().map(|x| x.abs_diff(median)).collect();
+        deviations.sort_unstable();
+        let mad = median_duration(&deviations);
+
+        // NOTE: a MAD of zero means that at least half of the samples are identical, keep all of them.
+        if !mad.is_zero() {
+            let max_deviation = mad.mul_f64(OUTLIER_THRESHOLD * MAD_SCALE);
+            samples.retain(|x| x.abs_diff(median) <= max_deviation);
+        }
+
+        let average = average_duration(&samples);
+
+        Stats {
+            average,
+            samples: total as u128,
+            min: samples[0],
+            max: samples[samples.len() - 1],
+            median: median_duration(&samples),
+            p5: percentile(&samples, 0.05),
+            p95: percentile(&samples, 0.95),
+            std_dev: std_dev(&samples, average),
+            outliers: (total - samples.len()) as u128,
+        }
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn average_duration(numbers: &[Duration]) -> Duration {
+    let nanos = numbers.iter().map(Duration::as_nanos).sum::<u128>() / numbers.len() as u128;
+    Duration::from_nanos(nanos as u64)
+}
+
+/// Expects `numbers` to be sorted.
+fn median_duration(numbers: &[Duration]) -> Duration {
+    let mid = numbers.len() / 2;
+
+    if numbers.len().is_multiple_of(2) {
+        (numbers[mid - 1] + numbers[mid]) / 2
+    } else {
+        numbers[mid]
+    }
+}
+
+/// Nearest-rank percentile, expects `numbers` to be sorted.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn percentile(numbers: &[Duration], p: f64) -> Duration {
+    let rank = (p * numbers.len() as f64).ceil() as usize;
+    numbers[rank.clamp(1, numbers.len()) - 1]
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn std_dev(numbers: &[Duration], average: Duration) -> Duration {
+    let average = average.as_secs_f64();
+
+    let variance = numbers
+        .iter()
+        .map(|x| (x.as_secs_f64() - average).powi(2))
+        .sum::<f64>()
+        / numbers.len() as f64;
+
+    Duration::from_secs_f64(variance.sqrt())
+}
+
+/// Represents the outcome of running a single solution part.
+#[derive(Clone, Debug)]
+pub struct PartReport {
+    pub puzzle: Puzzle,
+    pub part: u8,
+    pub answer: Option<String>,
+    /// The error of a part that failed.
+    pub error: Option<String>,
+    pub stats: Stats,
+}
+
+/// Print a set of part reports in one of the machine-readable formats.
+/// Reports are printed as they come in for [`Format::Ndjson`], so this only prints for [`Format::Json`].
+pub fn print_reports(reports: &[PartReport], format: Format) {
+    if format == Format::Json {
+        let json = JsonValue::Array(reports.iter().map(JsonValue::from).collect());
+        println!("{}", json.stringify().unwrap());
+    }
+}
+
+/// Print a single part report if it should be streamed in the given format.
+pub fn print_report(report: &PartReport, format: Format) {
+    if format == Format::Ndjson {
+        println!("{}", JsonValue::from(report).stringify().unwrap());
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn nanos(duration: Duration) -> JsonValue {
+    JsonValue::Number(duration.as_nanos() as f64)
+}
+
+impl From<&PartReport> for JsonValue {
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "edition".into(),
+            JsonValue::Number(f64::from(value.puzzle.edition.into_inner())),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::String(value.puzzle.day.to_string()),
+        );
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "error".into(),
+            match &value.error {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        #[allow(clippy::cast_precision_loss)]
+        let samples = value.stats.samples as f64;
+
+        map.insert("duration_nanos".into(), nanos(value.stats.average));
+        map.insert("samples".into(), JsonValue::Number(samples));
+        map.insert("min_nanos".into(), nanos(value.stats.min));
+        map.insert("max_nanos".into(), nanos(value.stats.max));
+        map.insert("median_nanos".into(), nanos(value.stats.median));
+        map.insert("p5_nanos".into(), nanos(value.stats.p5));
+        map.insert("p95_nanos".into(), nanos(value.stats.p95));
+        map.insert("std_dev_nanos".into(), nanos(value.stats.std_dev));
+
+        #[allow(clippy::cast_precision_loss)]
+        let outliers = value.stats.outliers as f64;
+        map.insert("outliers".into(), JsonValue::Number(outliers));
+
+        JsonValue::Object(map)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{collections::HashMap, time::Duration};
+
+    use tinyjson::JsonValue;
+
+    use super::{Format, PartReport, Stats};
+    use crate::puzzle;
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("human".parse::<Format>().unwrap(), Format::Human);
+        assert_eq!("json".parse::<Format>().unwrap(), Format::Json);
+        assert_eq!("ndjson".parse::<Format>().unwrap(), Format::Ndjson);
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn serializes_reports() {
+        let report = PartReport {
+            puzzle: puzzle!(2026 / 7),
+            part: 1,
+            answer: Some("866".into()),
+            error: None,
+            stats: Stats::from_samples(vec![
+                Duration::from_nanos(30),
+                Duration::from_nanos(10),
+                Duration::from_nanos(20),
+            ]),
+        };
+
+        let json = JsonValue::from(&report);
+        let map = json.get::<HashMap<String, JsonValue>>().unwrap();
+
+        assert_eq!(map["edition"], JsonValue::Number(2026.0));
+        assert_eq!(map["day"], JsonValue::String("07".into()));
+        assert_eq!(map["part"], JsonValue::Number(1.0));
+        assert_eq!(map["answer"], JsonValue::String("866".into()));
+        assert_eq!(map["samples"], JsonValue::Number(3.0));
+        assert_eq!(map["min_nanos"], JsonValue::Number(10.0));
+        assert_eq!(map["max_nanos"], JsonValue::Number(30.0));
+        assert_eq!(map["median_nanos"], JsonValue::Number(20.0));
+    }
+
+    #[test]
+    fn serializes_missing_answers() {
+        let report = PartReport {
+            puzzle: puzzle!(1),
+            part: 2,
+            answer: None,
+            error: Some("line 1, column 1: expected a number".into()),
+            stats: Stats::single(Duration::from_nanos(5)),
+        };
+
+        let json = JsonValue::from(&report);
+        let map = json.get::<HashMap<String, JsonValue>>().unwrap();
+
+        assert!(map["answer"].is_null());
+        assert_eq!(
+            map["error"],
+            JsonValue::String("line 1, column 1: expected a number".into())
+        );
+        assert_eq!(map["duration_nanos"], JsonValue::Number(5.0));
+    }
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn computes_stats() {
+        let stats = Stats::from_samples(nanos(&[5, 1, 4, 2, 3]));
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.average, Duration::from_nanos(3));
+        assert_eq!(stats.median, Duration::from_nanos(3));
+        assert_eq!(stats.min, Duration::from_nanos(1));
+        assert_eq!(stats.max, Duration::from_nanos(5));
+        assert_eq!(stats.p5, Duration::from_nanos(1));
+        assert_eq!(stats.p95, Duration::from_nanos(5));
+        assert_eq!(stats.std_dev.as_nanos(), 1);
+    }
+
+    #[test]
+    fn computes_percentiles() {
+        let samples: Vec<u64> = (1..=100).collect();
+        let stats = Stats::from_samples(nanos(&samples));
+        assert_eq!(stats.p5, Duration::from_nanos(5));
+        assert_eq!(stats.p95, Duration::from_nanos(95));
+        assert_eq!(stats.median, Duration::from_nanos(50));
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = Stats::from_samples(nanos(&[100, 101, 99, 100, 102, 98, 100, 5000]));
+        assert_eq!(stats.samples, 8);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.max, Duration::from_nanos(102));
+        assert_eq!(stats.average, Duration::from_nanos(100));
+    }
+
+    #[test]
+    fn keeps_identical_samples() {
+        let stats = Stats::from_samples(nanos(&[10, 10, 10, 10, 11]));
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.max, Duration::from_nanos(11));
+    }
+}
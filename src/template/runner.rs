@@ -1,35 +1,117 @@
 /// Encapsulates code that interacts with solution functions.
+use std::cmp;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-// use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp /* env, process */};
 
+use crate::template::report::{print_report, Format, PartReport, Stats};
 use crate::template::ANSI_BOLD;
-use crate::template::{/* aoc_cli ,*/ Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{PartResult, Puzzle, ANSI_ITALIC, ANSI_RESET};
+
+/// Default time spent benching a solution part.
+pub const DEFAULT_BUDGET: Duration = Duration::from_secs(1);
+
+/// Options that control how solution parts are run and reported.
+#[derive(Clone, Copy, Debug)]
+pub struct RunOptions {
+    pub is_timed: bool,
+    pub format: Format,
+    /// Approximate time spent benching a part, if the run is timed.
+    pub budget: Duration,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        RunOptions {
+            is_timed: false,
+            format: Format::default(),
+            budget: DEFAULT_BUDGET,
+        }
+    }
+}
+
+impl RunOptions {
+    /// Read the options from the `--time`, `--format` and `--budget` arguments passed to a solution binary.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+
+        let budget = args
+            .iter()
+            .position(|x| x == "--budget")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|millis| millis.parse().ok())
+            .map_or(DEFAULT_BUDGET, Duration::from_millis);
+
+        RunOptions {
+            is_timed: args.iter().any(|x| x == "--time"),
+            format: Format::from_args(),
+            budget,
+        }
+    }
+}
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, _day: Day, part: u8) {
+pub fn run_part<I: Copy, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: Puzzle,
+    part: u8,
+) -> PartReport {
+    run_part_with(func, input, puzzle, part, RunOptions::from_args())
+}
+
+/// Run a solution part and print its result in the requested format.
+pub fn run_part_with<I: Copy, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: Puzzle,
+    part: u8,
+    options: RunOptions,
+) -> PartReport {
     let part_str = format!("Part {part}");
+    let is_human = options.format.is_human();
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, stats) = run_timed(func, input, options, |result| {
+        if is_human {
+            print_result(result.as_answer(), &part_str, "");
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+            if options.is_timed {
+                print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+                let _ = stdout().flush();
+            }
+        }
+    });
+
+    if is_human {
+        print_result(result.as_answer(), &part_str, &format_duration(&stats));
+    }
 
-    // if let Some(result) = result {
-    //     submit_result(result, day, part);
-    // }
+    let (answer, error) = match result.as_answer() {
+        Ok(answer) => (answer.map(ToString::to_string), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+
+    let report = PartReport {
+        puzzle,
+        part,
+        answer,
+        error,
+        stats,
+    };
+
+    print_report(&report, options.format);
+    report
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Run a solution part. The behavior differs depending on whether the run is timed:
+///  1. if not, the function is executed once.
+///  2. if it is, the function is warmed up and benched (approx. the time budget or 10 samples, whatever take longer.)
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
+    options: RunOptions,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Stats) {
     let timer = Instant::now();
     let result = {
         #[cfg(feature = "dhat-heap")]
@@ -41,23 +123,31 @@ fn run_timed<I: Copy, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let stats = if options.is_timed {
+        bench(func, input, &base_time, options.budget)
     } else {
-        (base_time, 1)
+        Stats::single(base_time)
     };
 
-    (result, run.0, run.1)
+    (result, stats)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    budget: Duration,
+) -> Stats {
+    let base_nanos = cmp::max(base_time.as_nanos(), 10);
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    // NOTE: the initial run doubles as warm-up, which is all slow solutions get.
+    let warm_up_iterations = (budget.as_nanos() / 10 / base_nanos).min(1000);
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    for _ in 0..warm_up_iterations {
+        black_box(func(black_box(input)));
+    }
+
+    let bench_iterations = (budget.as_nanos() / base_nanos).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -67,34 +157,45 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    Stats::from_samples(timers)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
-}
-
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
+fn format_duration(stats: &Stats) -> String {
+    let Stats {
+        average: duration,
+        samples,
+        median,
+        p5,
+        p95,
+        std_dev,
+        outliers,
+        ..
+    } = stats;
+
+    if *samples == 1 {
         format!(" ({duration:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        let outliers_str = if *outliers > 0 {
+            format!(", {outliers} outliers")
+        } else {
+            String::new()
+        };
+
+        format!(
+            " ({duration:.1?} ± {std_dev:.1?}, median {median:.1?}, p5-p95 {p5:.1?}-{p95:.1?} @ {samples} samples{outliers_str})"
+        )
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(
+    result: Result<Option<&dyn Display>, &dyn Display>,
+    part: &str,
+    duration_str: &str,
+) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Ok(Some(result)) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -114,7 +215,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Ok(None) => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -122,44 +223,14 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Err(e) => {
+            if is_intermediate_result {
+                print!("{part}: ✖");
+            } else {
+                print!("\r");
+                println!("{part}: ✖ {ANSI_BOLD}error{ANSI_RESET}{duration_str}");
+                println!("{e}");
+            }
+        }
     }
 }
-
-// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-//  1. we are in `--release` mode.
-//  2. aoc-cli is installed.
-// fn submit_result<T: Display>(
-//     result: T,
-//     day: Day,
-//     part: u8,
-// ) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-//     let args: Vec<String> = env::args().collect();
-
-//     if !args.contains(&"--submit".into()) {
-//         return None;
-//     }
-
-//     if args.len() < 3 {
-//         eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-//         process::exit(1);
-//     }
-
-//     let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
-
-//     let Ok(part_submit) = args[part_index].parse::<u8>() else {
-//         eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-//         process::exit(1);
-//     };
-
-//     if part_submit != part {
-//         return None;
-//     }
-
-//     if aoc_cli::check().is_err() {
-//         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-//         process::exit(1);
-//     }
-
-//     println!("Submitting result via aoc-cli...");
-//     Some(aoc_cli::submit(day, part, &result.to_string()))
-// }
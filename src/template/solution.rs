@@ -0,0 +1,120 @@
+use std::fmt::Display;
+
+use crate::template::{Day, Edition, Input, Puzzle, DEFAULT_EDITION};
+
+/// A type-erased outcome of a solution part.
+pub enum Answer {
+    Solved(Box<dyn Display>),
+    /// The part is not implemented or did not produce an answer.
+    Unsolved,
+    /// The part failed, e.g. on malformed input.
+    Failed(Box<dyn Display>),
+}
+
+impl Answer {
+    /// Render the answer, or the error if the part failed.
+    pub fn to_strings(&self) -> Result<Option<String>, String> {
+        match self.as_answer() {
+            Ok(answer) => Ok(answer.map(ToString::to_string)),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+/// Return types accepted for solution parts: `Option<T>` for parts that may be unsolved
+/// and `Result<T, E>` for parts that can fail, e.g. with a [`crate::PuzzleError`].
+pub trait PartResult {
+    /// Borrow the answer, [`None`] if the part is unsolved. Returns the error if the part failed.
+    fn as_answer(&self) -> Result<Option<&dyn Display>, &dyn Display>;
+
+    /// Type-erase the result.
+    fn into_answer(self) -> Answer;
+}
+
+impl<T: Display + 'static> PartResult for Option<T> {
+    fn as_answer(&self) -> Result<Option<&dyn Display>, &dyn Display> {
+        Ok(self.as_ref().map(|x| x as &dyn Display))
+    }
+
+    fn into_answer(self) -> Answer {
+        match self {
+            Some(x) => Answer::Solved(Box::new(x)),
+            None => Answer::Unsolved,
+        }
+    }
+}
+
+impl<T: Display + 'static, E: Display + 'static> PartResult for Result<T, E> {
+    fn as_answer(&self) -> Result<Option<&dyn Display>, &dyn Display> {
+        match self {
+            Ok(x) => Ok(Some(x)),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn into_answer(self) -> Answer {
+        match self {
+            Ok(x) => Answer::Solved(Box::new(x)),
+            Err(e) => Answer::Failed(Box::new(e)),
+        }
+    }
+}
+
+impl PartResult for Answer {
+    fn as_answer(&self) -> Result<Option<&dyn Display>, &dyn Display> {
+        match self {
+            Answer::Solved(x) => Ok(Some(x.as_ref())),
+            Answer::Unsolved => Ok(None),
+            Answer::Failed(e) => Err(e.as_ref()),
+        }
+    }
+
+    fn into_answer(self) -> Answer {
+        self
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A solution registered by the `solution!` macro.
+///
+/// Every `src/bin/NN.rs` implements this trait, which allows the runner binary to
+/// execute solutions in-process instead of invoking `cargo run` for every day.
+pub trait Solution: Sync {
+    /// The puzzle this solution belongs to.
+    fn puzzle(&self) -> Puzzle;
+
+    /// The day this solution belongs to.
+    fn day(&self) -> Day {
+        self.puzzle().day
+    }
+
+    /// The parts (1 and/or 2) implemented by this solution.
+    fn parts(&self) -> &'static [u8];
+
+    /// Solves a single part for the provided input. Returns [`Answer::Unsolved`] for parts that are not implemented.
+    fn solve(&self, part: u8, input: &str) -> Answer;
+
+    /// Whether the parts of this solution stream through an [`Input`] instead of taking the whole input as `&str`.
+    fn is_streaming(&self) -> bool {
+        false
+    }
+
+    /// Solves a single part for an input that is read on demand.
+    /// Solutions that are not streaming read the whole input into memory first.
+    fn solve_input(&self, part: u8, input: &Input) -> Answer {
+        match input.read_to_string() {
+            Ok(text) => self.solve(part, &text),
+            Err(e) => Answer::Failed(Box::new(e)),
+        }
+    }
+}
+
+/// Returns the sorted editions that have at least one solution, always including the default edition.
+pub fn editions(solutions: &[&dyn Solution]) -> Vec<Edition> {
+    let mut editions: Vec<Edition> = solutions.iter().map(|s| s.puzzle().edition).collect();
+    editions.push(DEFAULT_EDITION);
+    editions.sort_unstable();
+    editions.dedup();
+    editions
+}
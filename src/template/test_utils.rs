@@ -0,0 +1,10 @@
+/// Fixtures shared by the tests of the template and of the utils.
+use std::{env, fs, path::PathBuf};
+
+/// An empty directory in the temporary directory of the system, named after `name` and this process.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("i18n-puzzles-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
@@ -1,16 +1,20 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::report::Stats;
+use crate::template::{Day, Edition, Puzzle, DEFAULT_EDITION};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
-/// Represents benchmark times for a single day.
+/// Represents benchmark times for a single puzzle.
 #[derive(Clone, Debug)]
 pub struct Timing {
-    pub day: Day,
+    pub puzzle: Puzzle,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    /// Statistics of the benchmark, not present for timings stored before they were recorded.
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
     pub total_nanos: f64,
 }
 
@@ -46,12 +50,12 @@ impl Timings {
         }
 
         for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
+            if !data.iter().any(|t| t.puzzle == timing.puzzle) {
                 data.push(timing.clone());
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.puzzle);
         Timings { data }
     }
 
@@ -60,10 +64,11 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
+    pub fn is_day_complete(&self, puzzle: impl Into<Puzzle>) -> bool {
+        let puzzle = puzzle.into();
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .any(|t| t.puzzle == puzzle && t.part_1.is_some() && t.part_2.is_some())
     }
 }
 
@@ -111,7 +116,11 @@ impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
-        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("edition".into(), edition_to_json(value.puzzle.edition));
+        map.insert(
+            "day".into(),
+            JsonValue::String(value.puzzle.day.to_string()),
+        );
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
         let part_1 = value.part_1.clone().map(JsonValue::String);
@@ -133,6 +142,16 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "part_1_stats".into(),
+            value.part_1_stats.map_or(JsonValue::Null, stats_to_json),
+        );
+
+        map.insert(
+            "part_2_stats".into(),
+            value.part_2_stats.map_or(JsonValue::Null, stats_to_json),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -151,6 +170,11 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
+        let edition = json
+            .get("edition")
+            .map_or(Ok(DEFAULT_EDITION), edition_from_json)
+            .map_err(|()| "Expected timing.edition to be a four digit number.")?;
+
         let part_1 = json
             .get("part_1")
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
@@ -166,10 +190,22 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        let part_1_stats = json
+            .get("part_1_stats")
+            .map_or(Ok(None), stats_from_json)
+            .map_err(|_| "Expected timing.part_1_stats to be null or stats object.")?;
+
+        let part_2_stats = json
+            .get("part_2_stats")
+            .map_or(Ok(None), stats_from_json)
+            .map_err(|_| "Expected timing.part_2_stats to be null or stats object.")?;
+
         Ok(Timing {
-            day,
+            puzzle: Puzzle::new(edition, day),
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_stats,
+            part_2_stats,
             total_nanos,
         })
     }
@@ -177,9 +213,92 @@ impl TryFrom<&JsonValue> for Timing {
 
 /* -------------------------------------------------------------------------- */
 
+pub(crate) fn edition_to_json(edition: Edition) -> JsonValue {
+    JsonValue::Number(f64::from(edition.into_inner()))
+}
+
+/// Parses the `edition` key of a stored puzzle. Files written before editions existed omit it.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn edition_from_json(value: &JsonValue) -> Result<Edition, ()> {
+    let year = value.get::<f64>().ok_or(())?;
+    Edition::new(*year as u16).ok_or(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+const STATS_KEYS: [&str; 9] = [
+    "average_nanos",
+    "samples",
+    "min_nanos",
+    "max_nanos",
+    "median_nanos",
+    "p5_nanos",
+    "p95_nanos",
+    "std_dev_nanos",
+    "outliers",
+];
+
+#[allow(clippy::cast_precision_loss)]
+fn stats_to_json(stats: Stats) -> JsonValue {
+    let values = [
+        stats.average.as_nanos(),
+        stats.samples,
+        stats.min.as_nanos(),
+        stats.max.as_nanos(),
+        stats.median.as_nanos(),
+        stats.p5.as_nanos(),
+        stats.p95.as_nanos(),
+        stats.std_dev.as_nanos(),
+        stats.outliers,
+    ];
+
+    let map: HashMap<String, JsonValue> = STATS_KEYS
+        .iter()
+        .zip(values)
+        .map(|(key, value)| ((*key).into(), JsonValue::Number(value as f64)))
+        .collect();
+
+    JsonValue::Object(map)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn stats_from_json(value: &JsonValue) -> Result<Option<Stats>, ()> {
+    if value.is_null() {
+        return Ok(None);
+    }
+
+    let json = value.get::<HashMap<String, JsonValue>>().ok_or(())?;
+
+    let mut values = [0_u64; STATS_KEYS.len()];
+
+    for (key, value) in STATS_KEYS.iter().zip(values.iter_mut()) {
+        *value = json
+            .get(*key)
+            .and_then(|v| v.get::<f64>())
+            .ok_or(())?
+            .round() as u64;
+    }
+
+    let [average, samples, min, max, median, p5, p95, std_dev, outliers] = values;
+
+    Ok(Some(Stats {
+        average: Duration::from_nanos(average),
+        samples: u128::from(samples),
+        min: Duration::from_nanos(min),
+        max: Duration::from_nanos(max),
+        median: Duration::from_nanos(median),
+        p5: Duration::from_nanos(p5),
+        p95: Duration::from_nanos(p95),
+        std_dev: Duration::from_nanos(std_dev),
+        outliers: u128::from(outliers),
+    }))
+}
+
+/* -------------------------------------------------------------------------- */
+
 #[cfg(feature = "test_lib")]
 mod tests {
-    use crate::day;
+    use crate::puzzle;
 
     use super::{Timing, Timings};
 
@@ -187,21 +306,27 @@ mod tests {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
+                    puzzle: puzzle!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
-                    day: day!(2),
+                    puzzle: puzzle!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
-                    day: day!(4),
+                    puzzle: puzzle!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 4e+10,
                 },
             ],
@@ -209,7 +334,7 @@ mod tests {
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{puzzle, template::timings::Timings};
 
         #[test]
         fn handles_json_timings() {
@@ -217,12 +342,31 @@ mod tests {
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
-            assert_eq!(timing.day, day!(1));
+            assert_eq!(timing.puzzle, puzzle!(1));
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_timings_with_editions() {
+            let json = r#"{ "data": [{ "edition": 2026, "day": "01", "part_1": null, "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].puzzle, puzzle!(2026 / 1));
+        }
+
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "average_nanos": 1000000, "samples": 100, "min_nanos": 900000, "max_nanos": 1200000, "median_nanos": 990000, "p5_nanos": 950000, "p95_nanos": 1100000, "std_dev_nanos": 20000, "outliers": 2 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let stats = timings.data[0].part_1_stats.unwrap();
+            assert_eq!(stats.samples, 100);
+            assert_eq!(stats.median.as_nanos(), 990_000);
+            assert_eq!(stats.p95.as_nanos(), 1_100_000);
+            assert_eq!(stats.outliers, 2);
+            assert!(timings.data[0].part_2_stats.is_none());
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -247,9 +391,26 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
-        use std::collections::HashMap;
+        use crate::template::{report::Stats, timings::Timings};
+        use std::{collections::HashMap, time::Duration};
         use tinyjson::JsonValue;
 
+        #[test]
+        fn round_trips_stats() {
+            let mut timings = get_mock_timings();
+            let stats = Stats::from_samples(vec![
+                Duration::from_nanos(10),
+                Duration::from_nanos(12),
+                Duration::from_nanos(11),
+            ]);
+            timings.data[0].part_1_stats = Some(stats);
+
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].part_1_stats, Some(stats));
+            assert_eq!(parsed.data[0].part_2_stats, None);
+        }
+
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
@@ -270,7 +431,7 @@ mod tests {
 
     mod is_day_complete {
         use crate::{
-            day,
+            day, puzzle,
             template::timings::{Timing, Timings},
         };
 
@@ -278,48 +439,54 @@ mod tests {
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
+                    puzzle: puzzle!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
+                    puzzle: puzzle!(1),
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
+                    puzzle: puzzle!(1),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
-            day,
+            puzzle,
             template::timings::{Timing, Timings},
         };
 
@@ -330,18 +497,20 @@ mod tests {
             let timings = get_mock_timings();
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(3),
+                    puzzle: puzzle!(3),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 0_f64,
                 }],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
-            assert_eq!(merged.data[0].day, day!(1));
-            assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[2].day, day!(3));
-            assert_eq!(merged.data[3].day, day!(4));
+            assert_eq!(merged.data[0].puzzle, puzzle!(1));
+            assert_eq!(merged.data[1].puzzle, puzzle!(2));
+            assert_eq!(merged.data[2].puzzle, puzzle!(3));
+            assert_eq!(merged.data[3].puzzle, puzzle!(4));
         }
 
         #[test]
@@ -350,19 +519,21 @@ mod tests {
 
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(2),
+                    puzzle: puzzle!(2),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 0_f64,
                 }],
             };
             let merged = timings.merge(&other);
 
             assert_eq!(merged.data.len(), 3);
-            assert_eq!(merged.data[0].day, day!(1));
-            assert_eq!(merged.data[1].day, day!(2));
+            assert_eq!(merged.data[0].puzzle, puzzle!(1));
+            assert_eq!(merged.data[1].puzzle, puzzle!(2));
             assert_eq!(merged.data[1].total_nanos, 0_f64);
-            assert_eq!(merged.data[2].day, day!(4));
+            assert_eq!(merged.data[2].puzzle, puzzle!(4));
         }
 
         #[test]
@@ -0,0 +1,146 @@
+/// Sniffing of byte order marks, U+FEFF encoded at the start of text to tell its encoding.
+///
+/// [`sniff`] recognizes the marks of the Unicode encodings and of GB18030, and tells where the
+/// text after the mark starts.
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Bom {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Utf32Le,
+    Utf32Be,
+    /// `+/v` and one of `8`, `9`, `+` or `/`, the last three holding the first bits of the next
+    /// character.
+    Utf7,
+    /// The Standard Compression Scheme for Unicode.
+    Scsu,
+    Gb18030,
+}
+
+impl Bom {
+    /// In the order they are sniffed, so that longer marks win over their prefixes e.g. UTF-32LE
+    /// over UTF-16LE.
+    pub const ALL: [Bom; 8] = [
+        Bom::Utf32Le,
+        Bom::Utf32Be,
+        Bom::Utf8,
+        Bom::Utf16Le,
+        Bom::Utf16Be,
+        Bom::Gb18030,
+        Bom::Scsu,
+        Bom::Utf7,
+    ];
+
+    /// The mark, as written before text for UTF-7.
+    pub fn bytes(self) -> &'static [u8] {
+        match self {
+            Bom::Utf8 => b"\xEF\xBB\xBF",
+            Bom::Utf16Le => b"\xFF\xFE",
+            Bom::Utf16Be => b"\xFE\xFF",
+            Bom::Utf32Le => b"\xFF\xFE\x00\x00",
+            Bom::Utf32Be => b"\x00\x00\xFE\xFF",
+            Bom::Utf7 => b"+/v8",
+            Bom::Scsu => b"\x0E\xFE\xFF",
+            Bom::Gb18030 => b"\x84\x31\x95\x33",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Bom::Utf8 => "UTF-8",
+            Bom::Utf16Le => "UTF-16LE",
+            Bom::Utf16Be => "UTF-16BE",
+            Bom::Utf32Le => "UTF-32LE",
+            Bom::Utf32Be => "UTF-32BE",
+            Bom::Utf7 => "UTF-7",
+            Bom::Scsu => "SCSU",
+            Bom::Gb18030 => "GB18030",
+        }
+    }
+
+    /// Where the text after the mark starts, if `bytes` start with it.
+    fn offset(self, bytes: &[u8]) -> Option<usize> {
+        if self != Bom::Utf7 {
+            return bytes
+                .starts_with(self.bytes())
+                .then_some(self.bytes().len());
+        }
+
+        let [b'+', b'/', b'v', last, rest @ ..] = bytes else {
+            return None;
+        };
+        let is_base64 = |b: &u8| b.is_ascii_alphanumeric() || *b == b'+' || *b == b'/';
+
+        // NOTE: the text only starts on a byte once the base64 of the mark ends, at a `-` or at
+        // any other byte. Otherwise it shares the base64 of the mark, so decode it all.
+        match (last, rest.first()) {
+            (b'8', Some(b'-')) => Some(5),
+            (b'8', next) if !next.is_some_and(is_base64) => Some(4),
+            (b'8' | b'9' | b'+' | b'/', _) => Some(0),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Bom {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// The byte order mark `bytes` start with and the offset of the text after it.
+///
+/// The offset is 0 for UTF-7 marks sharing their base64 with the text, which then starts with
+/// U+FEFF once decoded.
+pub fn sniff(bytes: &[u8]) -> Option<(Bom, usize)> {
+    Bom::ALL
+        .into_iter()
+        .find_map(|bom| Some((bom, bom.offset(bytes)?)))
+}
+
+/// The bytes after the byte order mark, if any.
+pub fn strip(bytes: &[u8]) -> &[u8] {
+    &bytes[sniff(bytes).map_or(0, |(_, offset)| offset)..]
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{sniff, strip, Bom};
+    use crate::utils::parse::parse_hex;
+
+    #[test]
+    fn sniffs_marks() {
+        for bom in Bom::ALL {
+            let mut bytes = bom.bytes().to_vec();
+            bytes.extend(b"-text");
+            let offset = if bom == Bom::Utf7 {
+                5
+            } else {
+                bom.bytes().len()
+            };
+            assert_eq!(sniff(&bytes), Some((bom, offset)), "{bom}");
+        }
+
+        let hex = parse_hex("fffe000000005400").unwrap();
+        assert_eq!(sniff(&hex), Some((Bom::Utf32Le, 4)));
+        let hex = parse_hex("fffe5400").unwrap();
+        assert_eq!(sniff(&hex), Some((Bom::Utf16Le, 2)));
+        assert_eq!(strip(&parse_hex("efbbbf6869").unwrap()), b"hi");
+        assert_eq!(sniff(b"\xEF\xBB"), None);
+        assert_eq!(strip(b"plain"), b"plain");
+    }
+
+    #[test]
+    fn sniffs_utf7_marks() {
+        assert_eq!(sniff(b"+/v8-Hi"), Some((Bom::Utf7, 5)));
+        assert_eq!(sniff(b"+/v8 Hi"), Some((Bom::Utf7, 4)));
+        assert_eq!(sniff(b"+/v8"), Some((Bom::Utf7, 4)));
+        assert_eq!(sniff(b"+/v9AOk-"), Some((Bom::Utf7, 0)));
+        assert_eq!(sniff(b"+/v8AOk-"), Some((Bom::Utf7, 0)));
+        assert_eq!(sniff(b"+/vA"), None);
+    }
+}
@@ -0,0 +1,403 @@
+/// Business hours of places in different time zones, as sets of intervals in UTC.
+///
+/// Intervals are [`Range`]s of minutes since the Unix epoch, which fit an `i32` for any year of
+/// interest. Business hours come as an [`IntervalSet`], so the hours of several calendars combine
+/// with its `union`, `intersection` and `difference`.
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use hashbrown::HashSet;
+
+use crate::utils::range::{IntervalSet, Range};
+use crate::utils::time::{resolve_local, Disambiguation};
+
+pub const MINUTES_PER_DAY: i32 = 24 * 60;
+
+/// Minutes since the Unix epoch of a date.
+pub fn minutes<T: TimeZone>(date: &DateTime<T>) -> i32 {
+    (date.timestamp().div_euclid(60)) as i32
+}
+
+/// The minutes of a year in UTC.
+pub fn year_range(year: i32) -> Range {
+    let start = |year| Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).unwrap();
+    Range::new(minutes(&start(year)), minutes(&start(year + 1)))
+}
+
+/// The days of the week without business hours.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Weekend(u8);
+
+impl Weekend {
+    pub const NONE: Weekend = Weekend(0);
+    pub const SATURDAY_SUNDAY: Weekend = Weekend(0b110_0000);
+    /// As in most of the Middle East.
+    pub const FRIDAY_SATURDAY: Weekend = Weekend(0b011_0000);
+    pub const SUNDAY: Weekend = Weekend(0b100_0000);
+
+    pub fn new(days: &[Weekday]) -> Self {
+        Weekend(
+            days.iter()
+                .fold(0, |mask, day| mask | 1 << day.num_days_from_monday()),
+        )
+    }
+
+    pub fn contains(self, day: Weekday) -> bool {
+        self.0 & 1 << day.num_days_from_monday() != 0
+    }
+}
+
+/// Business hours for each day of the week, in minutes since local midnight. Hours may run past
+/// midnight into the next day, e.g. `1320..1800` for a night shift from 22:00 to 6:00.
+#[derive(Clone, Debug, Default)]
+pub struct Schedule {
+    hours: [Vec<Range>; 7],
+}
+
+impl Schedule {
+    /// The same hours every day except on the weekend.
+    pub fn weekly(hours: Range, weekend: Weekend) -> Self {
+        let mut schedule = Schedule::default();
+        for (i, day_hours) in schedule.hours.iter_mut().enumerate() {
+            if weekend.0 & 1 << i == 0 {
+                day_hours.push(hours);
+            }
+        }
+        schedule
+    }
+
+    /// Replace the hours of one day, e.g. for half days. Empty `hours` make it a day off.
+    pub fn with_day(mut self, day: Weekday, hours: impl IntoIterator<Item = Range>) -> Self {
+        self.hours[day.num_days_from_monday() as usize] = hours.into_iter().collect();
+        self
+    }
+
+    pub fn hours(&self, day: Weekday) -> &[Range] {
+        &self.hours[day.num_days_from_monday() as usize]
+    }
+}
+
+/// A holiday that comes back every year.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HolidayRule {
+    /// The same date every year, e.g. `Fixed { month: 12, day: 25 }` for Christmas.
+    Fixed { month: u32, day: u32 },
+    /// The `n`th weekday of a month, counting from the end if negative, e.g. the last Monday of
+    /// May is `NthWeekday { month: 5, weekday: Weekday::Mon, n: -1 }`.
+    NthWeekday { month: u32, weekday: Weekday, n: i8 },
+    /// Days after (or before if negative) Easter Sunday, e.g. `Easter(-2)` for Good Friday.
+    Easter(i64),
+}
+
+impl HolidayRule {
+    /// The date of the holiday in `year`, if it falls in that year.
+    pub fn date(&self, year: i32) -> Option<NaiveDate> {
+        match *self {
+            HolidayRule::Fixed { month, day } => NaiveDate::from_ymd_opt(year, month, day),
+            HolidayRule::NthWeekday { month, weekday, n } if n > 0 => {
+                NaiveDate::from_weekday_of_month_opt(year, month, weekday, n as u8)
+            }
+            HolidayRule::NthWeekday { month, weekday, n } if n < 0 => {
+                let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+                let last = first
+                    .checked_add_months(chrono::Months::new(1))?
+                    .pred_opt()?;
+                let back = (last.weekday().num_days_from_monday() + 7
+                    - weekday.num_days_from_monday())
+                    % 7;
+                let date =
+                    last - Duration::days(i64::from(back) + 7 * (i64::from(n.unsigned_abs()) - 1));
+                (date.month() == month).then_some(date)
+            }
+            HolidayRule::NthWeekday { .. } => None,
+            HolidayRule::Easter(days) => {
+                let date = easter(year)? + Duration::days(days);
+                (date.year() == year).then_some(date)
+            }
+        }
+    }
+
+    pub fn matches(&self, date: NaiveDate) -> bool {
+        self.date(date.year()) == Some(date)
+    }
+}
+
+/// Easter Sunday of the Gregorian calendar, with the anonymous Gregorian algorithm.
+pub fn easter(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let (b, c) = (year / 100, year % 100);
+    let (d, e) = (b / 4, b % 4);
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let (i, k) = (c / 4, c % 4);
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+#[derive(Clone, Debug)]
+pub struct Calendar {
+    pub timezone: Tz,
+    /// Weekly schedules taking turns week by week, starting with the week of `rotation_start`.
+    /// A single schedule applies every week.
+    pub schedules: Vec<Schedule>,
+    pub rotation_start: NaiveDate,
+    /// Local dates without business hours.
+    pub holidays: HashSet<NaiveDate>,
+    pub holiday_rules: Vec<HolidayRule>,
+}
+
+impl Calendar {
+    /// Open `hours` (in minutes since local midnight, e.g. `510..1020` for 8:30 to 17:00) from
+    /// Monday to Friday.
+    pub fn new(timezone: Tz, hours: Range) -> Self {
+        Calendar::with_schedule(timezone, Schedule::weekly(hours, Weekend::SATURDAY_SUNDAY))
+    }
+
+    /// Open around the clock from Monday to Friday.
+    pub fn all_day(timezone: Tz) -> Self {
+        Calendar::new(timezone, Range::new(0, MINUTES_PER_DAY))
+    }
+
+    pub fn with_schedule(timezone: Tz, schedule: Schedule) -> Self {
+        Calendar::rotating(timezone, vec![schedule], NaiveDate::default())
+    }
+
+    /// Rotating shifts, `schedules` take turns week by week starting with the week of `start`.
+    pub fn rotating(timezone: Tz, schedules: Vec<Schedule>, start: NaiveDate) -> Self {
+        assert!(!schedules.is_empty(), "a calendar needs a schedule");
+
+        Calendar {
+            timezone,
+            schedules,
+            rotation_start: start,
+            holidays: HashSet::new(),
+            holiday_rules: vec![],
+        }
+    }
+
+    pub fn with_holidays(mut self, holidays: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.holidays.extend(holidays);
+        self
+    }
+
+    pub fn with_holiday_rules(mut self, rules: impl IntoIterator<Item = HolidayRule>) -> Self {
+        self.holiday_rules.extend(rules);
+        self
+    }
+
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holidays.contains(&date) || self.holiday_rules.iter().any(|r| r.matches(date))
+    }
+
+    /// The schedule of the week `date` is in.
+    pub fn schedule(&self, date: NaiveDate) -> &Schedule {
+        let monday = |date: NaiveDate| date.week(Weekday::Mon).first_day();
+        let weeks = (monday(date) - monday(self.rotation_start)).num_days() / 7;
+        &self.schedules[weeks.rem_euclid(self.schedules.len() as i64) as usize]
+    }
+
+    /// The hours of `date` in minutes since its local midnight, none on holidays.
+    pub fn hours(&self, date: NaiveDate) -> &[Range] {
+        if self.is_holiday(date) {
+            return &[];
+        }
+        self.schedule(date).hours(date.weekday())
+    }
+
+    pub fn is_working_day(&self, date: NaiveDate) -> bool {
+        !self.hours(date).is_empty()
+    }
+
+    /// Minutes since the epoch of a local time, `minute` minutes after the local midnight of `date`.
+    fn local_minutes(&self, date: NaiveDate, minute: i32) -> i32 {
+        let local: NaiveDateTime =
+            date.and_hms_opt(0, 0, 0).unwrap() + Duration::minutes(minute.into());
+
+        // NOTE: hours starting in a gap start once it's over, ones in a fold the first time round.
+        let date = resolve_local(&self.timezone, &local)
+            .and_then(|local| local.resolve(Disambiguation::ShiftForward))
+            .expect("date in range");
+        minutes(&date)
+    }
+
+    /// The business hours overlapping `range`, sorted and merged.
+    pub fn business_hours(&self, range: Range) -> IntervalSet<i32> {
+        let date_at = |minute: i32| {
+            let date = DateTime::from_timestamp(i64::from(minute) * 60, 0).expect("date in range");
+            date.with_timezone(&self.timezone).date_naive()
+        };
+
+        // NOTE: local dates differ from UTC ones by less than a day either way, and hours run at
+        // most a day past their date.
+        let mut hours = IntervalSet::new();
+        let mut date = date_at(range.start) - Duration::days(2);
+        let last = date_at(range.end) + Duration::days(1);
+
+        while date <= last {
+            for day_hours in self.hours(date) {
+                let start = self.local_minutes(date, day_hours.start);
+                let end = self.local_minutes(date, day_hours.end);
+
+                if let Some(day_hours) = Range::new(start, end).intersect(&range) {
+                    hours.insert(day_hours);
+                }
+            }
+            date = date.succ_opt().expect("date in range");
+        }
+
+        hours
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use chrono::{NaiveDate, Weekday};
+    use chrono_tz::Tz;
+
+    use super::{easter, year_range, Calendar, HolidayRule, Schedule, Weekend};
+    use crate::utils::range::{IntervalSet, Range};
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn computes_business_hours() {
+        let london = Calendar::new(Tz::Europe__London, Range::new(510, 1020));
+        let hours = london.business_hours(year_range(2022));
+
+        // 260 working days of eight and a half hours.
+        assert_eq!(hours.ranges().len(), 260);
+        assert_eq!(hours.total_length(), 260 * 510);
+
+        let london = london.with_holidays([date("2022-12-26")]);
+        let hours = london.business_hours(year_range(2022));
+        assert_eq!(hours.ranges().len(), 259);
+    }
+
+    #[test]
+    fn follows_dst_transitions() {
+        let minutes = |s: &str| super::minutes(&date(s).and_hms_opt(0, 0, 0).unwrap().and_utc());
+        let week = Range::new(minutes("2022-03-20"), minutes("2022-03-27"));
+
+        // Tehran skipped from midnight to 1:00 on Tuesday 2022-03-22, which had 23 hours.
+        let tehran = Calendar::all_day(Tz::Asia__Tehran).business_hours(week);
+        assert_eq!(tehran.total_length(), (5 * 24 - 1) * 60);
+
+        // the weekdays are back to back.
+        assert_eq!(tehran.ranges().len(), 1);
+        assert_eq!(
+            tehran.ranges()[0].start,
+            minutes("2022-03-20") + 20 * 60 + 30
+        );
+    }
+
+    #[test]
+    fn follows_schedules() {
+        let minutes = |s: &str| super::minutes(&date(s).and_hms_opt(0, 0, 0).unwrap().and_utc());
+        let week = Range::new(minutes("2024-06-03"), minutes("2024-06-10"));
+
+        // Sunday to Thursday, with a half day on Thursday.
+        let schedule = Schedule::weekly(Range::new(480, 960), Weekend::FRIDAY_SATURDAY)
+            .with_day(Weekday::Thu, [Range::new(480, 720)]);
+        let dubai = Calendar::with_schedule(Tz::Asia__Dubai, schedule);
+        assert!(!dubai.is_working_day(date("2024-06-07")));
+        assert!(dubai.is_working_day(date("2024-06-09")));
+        assert_eq!(dubai.business_hours(week).total_length(), 4 * 480 + 240);
+
+        // night shifts from 22:00 to 6:00, every other week.
+        let nights = Schedule::weekly(
+            Range::new(1320, 1800),
+            Weekend::new(&[Weekday::Sat, Weekday::Sun]),
+        );
+        let shifts = Calendar::rotating(
+            Tz::UTC,
+            vec![nights, Schedule::default()],
+            date("2024-06-03"),
+        );
+        let hours = shifts.business_hours(week);
+        assert_eq!(hours.ranges().len(), 5);
+        assert_eq!(hours.ranges()[0].start, minutes("2024-06-03") + 1320);
+        assert!(shifts
+            .business_hours(Range::new(minutes("2024-06-11"), minutes("2024-06-17")))
+            .is_empty());
+    }
+
+    #[test]
+    fn computes_holiday_rules() {
+        assert_eq!(easter(2024), Some(date("2024-03-31")));
+        assert_eq!(easter(2025), Some(date("2025-04-20")));
+        assert_eq!(easter(2038), Some(date("2038-04-25")));
+
+        let rules = [
+            (HolidayRule::Fixed { month: 12, day: 25 }, "2024-12-25"),
+            (HolidayRule::Easter(-2), "2024-03-29"),
+            (HolidayRule::Easter(50), "2024-05-20"),
+            (
+                HolidayRule::NthWeekday {
+                    month: 11,
+                    weekday: Weekday::Thu,
+                    n: 4,
+                },
+                "2024-11-28",
+            ),
+            (
+                HolidayRule::NthWeekday {
+                    month: 5,
+                    weekday: Weekday::Mon,
+                    n: -1,
+                },
+                "2024-05-27",
+            ),
+        ];
+        for (rule, expected) in rules {
+            assert_eq!(rule.date(2024), Some(date(expected)), "{rule:?}");
+        }
+        assert_eq!(
+            HolidayRule::NthWeekday {
+                month: 2,
+                weekday: Weekday::Mon,
+                n: 5
+            }
+            .date(2024),
+            None
+        );
+        assert_eq!(HolidayRule::Fixed { month: 2, day: 29 }.date(2023), None);
+
+        let london = Calendar::new(Tz::Europe__London, Range::new(510, 1020))
+            .with_holidays([date("2024-01-02")])
+            .with_holiday_rules([
+                HolidayRule::Fixed { month: 1, day: 1 },
+                HolidayRule::Easter(1),
+            ]);
+        assert!(!london.is_working_day(date("2024-01-01")));
+        assert!(!london.is_working_day(date("2024-01-02")));
+        assert!(!london.is_working_day(date("2024-04-01")));
+        assert!(london.is_working_day(date("2024-01-03")));
+    }
+
+    #[test]
+    fn combines_calendars() {
+        let year = year_range(2022);
+        let tokyo = Calendar::all_day(Tz::Asia__Tokyo).business_hours(year);
+        let offices = [Tz::Europe__Amsterdam, Tz::America__New_York]
+            .into_iter()
+            .map(|tz| Calendar::new(tz, Range::new(510, 1020)).business_hours(year))
+            .fold(IntervalSet::new(), |offices, hours| offices.union(&hours));
+
+        let covered = tokyo.intersection(&offices);
+        let uncovered = tokyo.difference(&offices);
+        assert_eq!(
+            covered.total_length() + uncovered.total_length(),
+            tokyo.total_length()
+        );
+        assert!(covered.total_length() > 0);
+        assert_eq!(uncovered, offices.complement(year).intersection(&tokyo));
+    }
+}
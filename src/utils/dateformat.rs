@@ -0,0 +1,365 @@
+/// Formatting of dates the way locales write them, with CLDR's patterns.
+///
+/// A [`DateFormatter`] renders dates in the date and time [`Style`]s of a [`Locale`], and
+/// [`format_pattern`] renders any CLDR pattern, e.g. `yyyy-MM-dd'T'HH:mm:ssxxx` for ISO 8601.
+use chrono::{DateTime, Datelike, Offset, Timelike};
+use chrono_tz::{OffsetName, Tz};
+
+use crate::utils::locale::{parse_pattern, HourCycle, Locale, PatternItem, Style};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DateFormatter {
+    locale: Locale,
+    date_style: Option<Style>,
+    time_style: Option<Style>,
+    hour_cycle: HourCycle,
+}
+
+impl DateFormatter {
+    /// Medium dates and short times in the hour cycle of `locale`, e.g. `Mar 5, 2024, 2:30 PM`.
+    pub fn new(locale: Locale) -> Self {
+        DateFormatter {
+            locale,
+            date_style: Some(Style::Medium),
+            time_style: Some(Style::Short),
+            hour_cycle: locale.hour_cycle(),
+        }
+    }
+
+    /// The style of the date, `None` to leave it out.
+    pub fn with_date_style(mut self, style: Option<Style>) -> Self {
+        self.date_style = style;
+        self
+    }
+
+    /// The style of the time, `None` to leave it out.
+    pub fn with_time_style(mut self, style: Option<Style>) -> Self {
+        self.time_style = style;
+        self
+    }
+
+    pub fn with_hour_cycle(mut self, hour_cycle: HourCycle) -> Self {
+        self.hour_cycle = hour_cycle;
+        self
+    }
+
+    /// The CLDR pattern dates are formatted with, e.g. `MMM d, y, h:mm a`.
+    pub fn pattern(&self) -> String {
+        let date = self.date_style.map(|style| self.locale.date_pattern(style));
+        let time = self.time_style.map(|style| {
+            let pattern = self.locale.time_pattern(style);
+            change_hour_cycle(self.locale, pattern, self.hour_cycle)
+        });
+
+        match (self.date_style, date, time) {
+            (Some(style), Some(date), Some(time)) => self
+                .locale
+                .date_time_pattern(style)
+                .replace("{1}", date)
+                .replace("{0}", &time),
+            (_, Some(date), None) => date.to_string(),
+            (_, None, Some(time)) => time,
+            _ => String::new(),
+        }
+    }
+
+    pub fn format(&self, date: &DateTime<Tz>) -> String {
+        format_pattern(date, &self.pattern(), self.locale)
+    }
+}
+
+/// `pattern`, a time pattern of `locale`, with hours in `hour_cycle`.
+fn change_hour_cycle(locale: Locale, pattern: &str, hour_cycle: HourCycle) -> String {
+    if hour_cycle == locale.hour_cycle() {
+        return pattern.to_string();
+    }
+
+    let mut items = parse_pattern(pattern);
+    match hour_cycle {
+        HourCycle::H12 => {
+            let hour = items
+                .iter()
+                .position(|item| matches!(item, PatternItem::Field('H', _)))
+                .unwrap_or(0);
+            let last = items
+                .iter()
+                .rposition(|item| matches!(item, PatternItem::Field('H' | 'm' | 's', _)))
+                .unwrap_or(0);
+
+            for item in items.iter_mut() {
+                if let PatternItem::Field(c @ 'H', _) = item {
+                    *c = 'h';
+                }
+            }
+
+            // NOTE: Japanese puts the period before the hour, e.g. `午後2:30`.
+            if locale == Locale::Japanese {
+                items.insert(hour, PatternItem::Field('a', 1));
+            } else {
+                items.insert(last + 1, PatternItem::Field('a', 1));
+                items.insert(last + 1, PatternItem::Literal(" ".to_string()));
+            }
+        }
+        HourCycle::H23 => {
+            for item in items.iter_mut() {
+                if let PatternItem::Field(c @ 'h', _) = item {
+                    *c = 'H';
+                }
+            }
+
+            // Drop the period and the spaces before it
+            if let Some(i) = items
+                .iter()
+                .position(|item| *item == PatternItem::Field('a', 1))
+            {
+                items.remove(i);
+                if let Some(PatternItem::Literal(literal)) = i.checked_sub(1).map(|i| &mut items[i])
+                {
+                    literal.truncate(literal.trim_end().len());
+                }
+            }
+        }
+    }
+
+    write_pattern(&items)
+}
+
+fn write_pattern(items: &[PatternItem]) -> String {
+    let mut pattern = String::new();
+    for item in items {
+        match item {
+            PatternItem::Field(c, count) => pattern.extend(std::iter::repeat_n(c, *count)),
+            PatternItem::Literal(literal)
+                if literal.contains(|c: char| c.is_ascii_alphabetic()) =>
+            {
+                pattern.push_str(&format!("'{}'", literal.replace('\'', "''")));
+            }
+            PatternItem::Literal(literal) => pattern.push_str(&literal.replace('\'', "''")),
+        }
+    }
+
+    pattern
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Format `date` with a CLDR pattern in `locale`.
+///
+/// Supports eras (`G`), years (`y`, `yy` for two digits), months (`M`, `MM`, `MMM`, `MMMM`), days
+/// (`d`), weekdays (`E`, `EEEE`), periods (`a`), hours (`h`, `H`), minutes (`m`), seconds (`s`),
+/// fractions of seconds (`S`) and zones: `z` abbreviations, `zzzz` names, `O` localized offsets
+/// and `x`, `X` ISO 8601 offsets. Other letters are kept as they are.
+pub fn format_pattern(date: &DateTime<Tz>, pattern: &str, locale: Locale) -> String {
+    let mut result = String::new();
+    let pad = |value: u32, count: usize| format!("{value:0count$}");
+
+    for item in parse_pattern(pattern) {
+        let (c, count) = match item {
+            PatternItem::Literal(literal) => {
+                result.push_str(&literal);
+                continue;
+            }
+            PatternItem::Field(c, count) => (c, count),
+        };
+
+        let year_of_era = date.year().max(1 - date.year()).unsigned_abs();
+        let month = date.month0() as usize;
+        let weekday = date.weekday().num_days_from_monday() as usize;
+
+        let field = match (c, count) {
+            ('G', _) => locale.eras()[usize::from(date.year() > 0)].to_string(),
+            ('y', 2) => pad(year_of_era % 100, 2),
+            ('y', _) => pad(year_of_era, count),
+            ('M', 3) => locale.months_abbreviated()[month].to_string(),
+            ('M', 4..) => locale.months()[month].to_string(),
+            ('M', _) => pad(date.month(), count),
+            ('d', _) => pad(date.day(), count),
+            ('E', ..=3) => locale.weekdays_abbreviated()[weekday].to_string(),
+            ('E', _) => locale.weekdays()[weekday].to_string(),
+            ('a', _) => locale.day_periods()[usize::from(date.hour() >= 12)].to_string(),
+            ('h', _) => pad(date.hour12().1, count),
+            ('H', _) => pad(date.hour(), count),
+            ('m', _) => pad(date.minute(), count),
+            ('s', _) => pad(date.second(), count),
+            ('S', _) => {
+                format!("{:09}", date.nanosecond() % 1_000_000_000)[..count.min(9)].to_string()
+            }
+            ('z', ..=3) => zone_abbreviation(date, locale),
+            ('z', _) => zone_name(date, locale),
+            ('O', 4..) => localized_offset(date, locale, true),
+            ('O', _) => localized_offset(date, locale, false),
+            ('x' | 'X', _) => iso_offset(date, c == 'X', count),
+            _ => std::iter::repeat_n(c, count).collect(),
+        };
+        result.push_str(&field);
+    }
+
+    result
+}
+
+fn offset_seconds(date: &DateTime<Tz>) -> i32 {
+    date.offset().fix().local_minus_utc()
+}
+
+/// The abbreviation of the zone if it has a name, e.g. `CET` but not `+03`.
+fn zone_abbreviation(date: &DateTime<Tz>, locale: Locale) -> String {
+    match date.offset().abbreviation() {
+        Some(abbreviation) if abbreviation.chars().all(|c| c.is_ascii_alphabetic()) => {
+            abbreviation.to_string()
+        }
+        _ => localized_offset(date, locale, false),
+    }
+}
+
+/// The name of the zone after its main city, e.g. `Paris Time`, or its offset for zones of no
+/// place such as `UTC`.
+fn zone_name(date: &DateTime<Tz>, locale: Locale) -> String {
+    let id = date.timezone().name();
+    match id.rsplit_once('/') {
+        Some((area, city)) if area != "Etc" => locale
+            .region_format()
+            .replace("{0}", &city.replace('_', " ")),
+        _ => localized_offset(date, locale, true),
+    }
+}
+
+/// The offset from UTC in the GMT format of `locale`, e.g. `GMT+1` or `GMT+01:00` when `long`.
+fn localized_offset(date: &DateTime<Tz>, locale: Locale, long: bool) -> String {
+    let seconds = offset_seconds(date);
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let (hours, minutes) = (seconds.abs() / 3600, seconds.abs() / 60 % 60);
+
+    let offset = match (seconds, long, minutes) {
+        (0, _, _) => String::new(),
+        (_, true, _) => format!("{sign}{hours:02}:{minutes:02}"),
+        (_, false, 0) => format!("{sign}{hours}"),
+        (_, false, _) => format!("{sign}{hours}:{minutes:02}"),
+    };
+
+    locale.gmt_format().replace("{0}", &offset)
+}
+
+/// The offset from UTC in ISO 8601, `+01`, `+0100` or `+01:00` as `count` goes from 1 to 3, and
+/// `Z` for UTC if `utc_as_z`.
+fn iso_offset(date: &DateTime<Tz>, utc_as_z: bool, count: usize) -> String {
+    let seconds = offset_seconds(date);
+    if seconds == 0 && utc_as_z {
+        return "Z".to_string();
+    }
+
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let (hours, minutes) = (seconds.abs() / 3600, seconds.abs() / 60 % 60);
+
+    match count {
+        1 if minutes == 0 => format!("{sign}{hours:02}"),
+        1 | 2 => format!("{sign}{hours:02}{minutes:02}"),
+        _ => format!("{sign}{hours:02}:{minutes:02}"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use chrono::{DateTime, TimeZone};
+    use chrono_tz::Tz;
+
+    use super::{format_pattern, DateFormatter};
+    use crate::utils::locale::{HourCycle, Locale, Style};
+    use crate::utils::time::parse_natural_local;
+
+    fn date(tz: Tz, year: i32) -> DateTime<Tz> {
+        tz.with_ymd_and_hms(year, 3, 5, 14, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn formats_styles() {
+        let paris = date(Tz::Europe__Paris, 2024);
+        let format = |locale, date_style, time_style| {
+            DateFormatter::new(locale)
+                .with_date_style(date_style)
+                .with_time_style(time_style)
+                .format(&paris)
+        };
+
+        assert_eq!(
+            DateFormatter::new(Locale::English).format(&paris),
+            "Mar 5, 2024, 2:30 PM"
+        );
+        assert_eq!(
+            format(Locale::English, Some(Style::Long), Some(Style::Long)),
+            "March 5, 2024 at 2:30:00 PM CET"
+        );
+        assert_eq!(
+            format(Locale::German, Some(Style::Full), Some(Style::Full)),
+            "Dienstag, 5. März 2024 um 14:30:00 Paris (Ortszeit)"
+        );
+        assert_eq!(
+            format(Locale::French, Some(Style::Short), Some(Style::Short)),
+            "05/03/2024 14:30"
+        );
+        assert_eq!(
+            format(Locale::Polish, Some(Style::Long), None),
+            "5 marca 2024"
+        );
+        assert_eq!(
+            format(Locale::Japanese, None, Some(Style::Medium)),
+            "14:30:00"
+        );
+    }
+
+    #[test]
+    fn changes_hour_cycles() {
+        let paris = date(Tz::Europe__Paris, 2024);
+
+        let japanese = DateFormatter::new(Locale::Japanese)
+            .with_date_style(Some(Style::Long))
+            .with_hour_cycle(HourCycle::H12);
+        assert_eq!(japanese.pattern(), "y年M月d日 ah:mm");
+        assert_eq!(japanese.format(&paris), "2024年3月5日 午後2:30");
+
+        let english = DateFormatter::new(Locale::English).with_hour_cycle(HourCycle::H23);
+        assert_eq!(english.pattern(), "MMM d, y, H:mm");
+        assert_eq!(english.format(&paris), "Mar 5, 2024, 14:30");
+
+        let german = DateFormatter::new(Locale::German).with_hour_cycle(HourCycle::H12);
+        assert_eq!(german.format(&paris), "05.03.2024, 02:30 PM");
+    }
+
+    #[test]
+    fn formats_patterns() {
+        let iso = "yyyy-MM-dd'T'HH:mm:ssxxx";
+        assert_eq!(
+            format_pattern(&date(Tz::Europe__Paris, 2024), iso, Locale::English),
+            "2024-03-05T14:30:00+01:00"
+        );
+        assert_eq!(
+            format_pattern(&date(Tz::UTC, 2024), "HH:mmX zzzz", Locale::French),
+            "14:30Z UTC"
+        );
+        assert_eq!(
+            format_pattern(&date(Tz::Asia__Kolkata, 2024), "z O OOOO", Locale::English),
+            "IST GMT+5:30 GMT+05:30"
+        );
+        assert_eq!(
+            format_pattern(&date(Tz::UTC, -43), "d MMMM y G", Locale::English),
+            "5 March 44 BC"
+        );
+        assert_eq!(
+            format_pattern(&date(Tz::UTC, 1999), "EEE d MMM yy", Locale::Danish),
+            "fre. 5 mar. 99"
+        );
+    }
+
+    #[test]
+    fn parses_what_it_formats() {
+        let paris = date(Tz::Europe__Paris, 2024);
+        for locale in Locale::ALL {
+            let formatted = DateFormatter::new(locale)
+                .with_date_style(Some(Style::Long))
+                .format(&paris);
+            let parsed = parse_natural_local(&formatted, &[locale]);
+            assert_eq!(parsed.ok(), Some(paris.naive_local()), "{formatted}");
+        }
+    }
+}
@@ -0,0 +1,345 @@
+/// Inference of the format of dates written with an unknown field order, e.g. `03/04/05`.
+///
+/// Given a batch of dates from one source, [`infer_formats`] finds the separators, the order of
+/// day, month and year, whether years have two or four digits and, for months written out, the
+/// locale of their names. Every format parsing at least one date is returned, most likely first.
+use std::fmt;
+
+use chrono::NaiveDate;
+
+use crate::utils::locale::Locale;
+use crate::utils::misc::rank_by_odds;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Field {
+    Day,
+    Month,
+    Year,
+}
+
+/// All the orders of the fields, the most common first.
+pub const ORDERS: [[Field; 3]; 6] = [
+    [Field::Year, Field::Month, Field::Day],
+    [Field::Day, Field::Month, Field::Year],
+    [Field::Month, Field::Day, Field::Year],
+    [Field::Year, Field::Day, Field::Month],
+    [Field::Day, Field::Year, Field::Month],
+    [Field::Month, Field::Year, Field::Day],
+];
+
+/// The default pivot of two-digit years, as for chrono's `%y`: `00` to `69` are 2000 to 2069 and
+/// `70` to `99` are 1970 to 1999.
+pub const DEFAULT_PIVOT: u8 = 70;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum YearStyle {
+    Full,
+    /// Two-digit years, those below `pivot` in the 21st century and the others in the 20th.
+    Short {
+        pivot: u8,
+    },
+}
+
+impl YearStyle {
+    pub fn year(self, yy: u8) -> i32 {
+        match self {
+            YearStyle::Full => i32::from(yy),
+            YearStyle::Short { pivot } if yy < pivot => 2000 + i32::from(yy),
+            YearStyle::Short { .. } => 1900 + i32::from(yy),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MonthStyle {
+    Numeric,
+    /// Full or abbreviated names.
+    Name(Locale),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DateFormat {
+    pub order: [Field; 3],
+    /// What comes between the first and second fields, and between the second and third.
+    pub separators: [String; 2],
+    pub year: YearStyle,
+    pub month: MonthStyle,
+}
+
+impl DateFormat {
+    pub fn parse(&self, date_str: &str) -> Option<NaiveDate> {
+        let (fields, separators) = tokenize(date_str)?;
+        if separators != self.separators {
+            return None;
+        }
+
+        let (mut year, mut month, mut day) = (None, None, None);
+        for (field, token) in self.order.iter().zip(fields) {
+            match field {
+                Field::Year => year = self.parse_year(token),
+                Field::Month => month = self.parse_month(token),
+                Field::Day => day = parse_number(token, 1..=2),
+            }
+        }
+
+        NaiveDate::from_ymd_opt(year?, month?, day?)
+    }
+
+    fn parse_year(&self, token: &str) -> Option<i32> {
+        match self.year {
+            YearStyle::Full => parse_number(token, 4..=4).map(|y| y as i32),
+            YearStyle::Short { .. } => parse_number(token, 2..=2).map(|y| self.year.year(y as u8)),
+        }
+    }
+
+    fn parse_month(&self, token: &str) -> Option<u32> {
+        match self.month {
+            MonthStyle::Numeric => parse_number(token, 1..=2),
+            MonthStyle::Name(locale) => locale.parse_month(token),
+        }
+    }
+}
+
+/// The format in chrono's syntax, e.g. `%d/%m/%y`, with `%B` for month names in any locale.
+impl fmt::Display for DateFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, field) in self.order.iter().enumerate() {
+            let specifier = match field {
+                Field::Day => "%d",
+                Field::Month if self.month == MonthStyle::Numeric => "%m",
+                Field::Month => "%B",
+                Field::Year if self.year == YearStyle::Full => "%Y",
+                Field::Year => "%y",
+            };
+            write!(f, "{specifier}")?;
+            if i < 2 {
+                write!(f, "{}", self.separators[i])?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_number(token: &str, digits: std::ops::RangeInclusive<usize>) -> Option<u32> {
+    if !digits.contains(&token.len()) || !token.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    token.parse().ok()
+}
+
+/// The three fields of a date and the two separators between them.
+fn tokenize(date_str: &str) -> Option<([&str; 3], [String; 2])> {
+    let date_str = date_str.trim();
+    let mut fields = vec![];
+    let mut separators = vec![];
+    let mut rest = date_str;
+
+    while !rest.is_empty() {
+        let end = rest
+            .find(|c: char| !c.is_alphanumeric())
+            .unwrap_or(rest.len());
+        let (field, after) = rest.split_at(end);
+        // NOTE: a period ending an abbreviated month belongs to the field, e.g. `févr. 2024`.
+        let period = usize::from(after.starts_with('.') && field.chars().all(char::is_alphabetic));
+        fields.push(&rest[..end + period]);
+
+        let after = &after[period..];
+        let end = after.find(char::is_alphanumeric).unwrap_or(after.len());
+        if end < after.len() {
+            separators.push(after[..end].to_string());
+        } else if end > 0 {
+            return None;
+        }
+        rest = &after[end..];
+    }
+
+    Some((fields.try_into().ok()?, separators.try_into().ok()?))
+}
+
+/// The pivot placing all `years` in one century window, the default one if it does.
+///
+/// The window starts right after the largest gap between the years, going around from `99` to `00`.
+pub fn infer_pivot(years: impl IntoIterator<Item = u8>) -> u8 {
+    let mut years: Vec<u8> = years.into_iter().collect();
+    years.sort_unstable();
+    years.dedup();
+    if years.is_empty() {
+        return DEFAULT_PIVOT;
+    }
+
+    // The largest gap from a year to the next one
+    let (before, after) = (0..years.len())
+        .map(|i| (years[i], years[(i + 1) % years.len()]))
+        .max_by_key(|&(a, b)| (i16::from(b) - i16::from(a)).rem_euclid(100))
+        .unwrap();
+
+    let in_gap = if before < after {
+        before < DEFAULT_PIVOT && DEFAULT_PIVOT <= after
+    } else {
+        before < DEFAULT_PIVOT || DEFAULT_PIVOT <= after
+    };
+
+    if in_gap {
+        DEFAULT_PIVOT
+    } else {
+        after
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Debug)]
+pub struct Candidate {
+    pub format: DateFormat,
+    /// How many dates the format parses.
+    pub matches: usize,
+    /// The probability of the format among all candidates, between 0 and 1.
+    pub confidence: f64,
+}
+
+// The odds of a date not matching the format of its source, e.g. a typo
+const MISMATCH_ODDS: f64 = 0.01;
+
+// Weights of the orders, as in `ORDERS`, to rank formats matching the same dates
+const ORDER_PRIORS: [f64; 6] = [4.0, 3.0, 2.0, 1.0, 0.5, 0.5];
+
+/// The formats parsing at least one of `dates`, ranked by confidence.
+///
+/// Each format is weighted by the odds of the dates it does not parse, and ties between formats
+/// parsing the same dates are broken by how common their field order is. Formats reading different
+/// dates for the same strings thus share the confidence, e.g. both `%d/%m/%Y` and `%m/%d/%Y`
+/// when no day is above 12.
+pub fn infer_formats<S: AsRef<str>>(dates: &[S]) -> Vec<Candidate> {
+    let tokenized: Vec<_> = dates.iter().filter_map(|d| tokenize(d.as_ref())).collect();
+
+    // The separators of most dates
+    let Some(separators) = most_common(tokenized.iter().map(|(_, s)| s.clone())) else {
+        return vec![];
+    };
+
+    let mut candidates = vec![];
+    for (order, prior) in ORDERS.iter().zip(ORDER_PRIORS) {
+        let position = |field| order.iter().position(|f| *f == field).unwrap();
+        let tokens = |field| {
+            tokenized
+                .iter()
+                .map(move |(fields, _)| fields[position(field)])
+        };
+
+        let year = if most_common(tokens(Field::Year).map(str::len)) == Some(2) {
+            let years = tokens(Field::Year).filter_map(|t| parse_number(t, 2..=2));
+            YearStyle::Short {
+                pivot: infer_pivot(years.map(|y| y as u8)),
+            }
+        } else {
+            YearStyle::Full
+        };
+
+        let months = std::iter::once(MonthStyle::Numeric)
+            .chain(Locale::ALL.into_iter().map(MonthStyle::Name));
+
+        for month in months {
+            let format = DateFormat {
+                order: *order,
+                separators: separators.clone(),
+                year,
+                month,
+            };
+            let matches = dates
+                .iter()
+                .filter(|d| format.parse(d.as_ref()).is_some())
+                .count();
+
+            if matches > 0 {
+                let odds = MISMATCH_ODDS.ln() * (dates.len() - matches) as f64 + prior.ln();
+                candidates.push(((format, matches), odds));
+            }
+        }
+    }
+
+    rank_by_odds(candidates)
+        .into_iter()
+        .map(|((format, matches), confidence)| Candidate {
+            format,
+            matches,
+            confidence,
+        })
+        .collect()
+}
+
+fn most_common<T: PartialEq>(items: impl Iterator<Item = T>) -> Option<T> {
+    let mut counts: Vec<(T, usize)> = vec![];
+    for item in items {
+        match counts.iter_mut().find(|(other, _)| *other == item) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((item, 1)),
+        }
+    }
+
+    // The first one seen on ties
+    counts
+        .into_iter()
+        .rev()
+        .max_by_key(|&(_, count)| count)
+        .map(|(item, _)| item)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::{infer_formats, infer_pivot, Field, MonthStyle, YearStyle, DEFAULT_PIVOT};
+    use crate::utils::locale::Locale;
+
+    #[test]
+    fn infers_numeric_formats() {
+        let candidates = infer_formats(&["25/12/2023", "01/02/2024", "13/07/1999"]);
+        assert_eq!(candidates[0].format.to_string(), "%d/%m/%Y");
+        assert_eq!(candidates[0].matches, 3);
+        assert!(candidates[0].confidence > 0.99);
+
+        // Without a day above 12 both orders match, the most common first.
+        let candidates = infer_formats(&["01.02.03", "04.05.06"]);
+        let formats: Vec<_> = candidates.iter().map(|c| c.format.to_string()).collect();
+        assert_eq!(formats[..3], ["%y.%m.%d", "%d.%m.%y", "%m.%d.%y"]);
+        assert!((candidates.iter().map(|c| c.confidence).sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn infers_month_names() {
+        let candidates = infer_formats(&["5 janv. 2024", "14 juillet 1789", "1 mai 2000"]);
+        let best = &candidates[0].format;
+        assert_eq!(best.order, [Field::Day, Field::Month, Field::Year]);
+        assert_eq!(best.month, MonthStyle::Name(Locale::French));
+        assert_eq!(
+            best.parse("14 juillet 1789"),
+            NaiveDate::from_ymd_opt(1789, 7, 14)
+        );
+
+        let candidates = infer_formats(&["March 3, 2021", "May 17, 2020"]);
+        assert_eq!(candidates[0].format.separators, [" ", ", "]);
+        assert_eq!(
+            candidates[0].format.month,
+            MonthStyle::Name(Locale::English)
+        );
+    }
+
+    #[test]
+    fn infers_pivot_windows() {
+        assert_eq!(infer_pivot([1, 45, 99]), DEFAULT_PIVOT);
+        assert_eq!(infer_pivot([1, 40, 75]), 40);
+        assert_eq!(YearStyle::Short { pivot: 40 }.year(39), 2039);
+
+        let candidates = infer_formats(&["75-03-01", "40-12-24", "01-05-05"]);
+        let best = &candidates[0].format;
+        assert_eq!(best.year, YearStyle::Short { pivot: 40 });
+        assert_eq!(
+            best.parse("40-12-24"),
+            NaiveDate::from_ymd_opt(1940, 12, 24)
+        );
+    }
+}
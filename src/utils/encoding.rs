@@ -0,0 +1,322 @@
+/// Detection of the encoding of text from its bytes.
+///
+/// [`detect`] decodes the bytes with every [`Charset`] that accepts them and scores each text by
+/// how likely its characters are: a model of text assigns each class of characters (ASCII, Latin
+/// letters, Cyrillic, CJK, controls, ...) a share of the characters of typical text, and penalizes
+/// letters of different scripts or of odd case within a word, e.g. the `rÃ¶` of UTF-8 read as
+/// Latin-1.
+use std::fmt;
+
+use encoding_rs::Encoding;
+
+use crate::utils::bom::Bom;
+use crate::utils::misc::rank_by_odds;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Charset {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Utf32Le,
+    Utf32Be,
+    /// ISO-8859-1 proper, which `encoding_rs` treats as Windows-1252.
+    Latin1,
+    /// The other encodings of the WHATWG Encoding Standard, e.g. Windows-1252 or Shift_JIS.
+    Legacy(&'static Encoding),
+}
+
+impl Charset {
+    /// The candidates of [`detect`], the most common first.
+    pub fn all() -> Vec<Charset> {
+        let unicode = [
+            Charset::Utf8,
+            Charset::Utf16Le,
+            Charset::Utf16Be,
+            Charset::Utf32Le,
+            Charset::Utf32Be,
+            Charset::Legacy(encoding_rs::WINDOWS_1252),
+            Charset::Latin1,
+        ];
+        let legacy = [
+            encoding_rs::ISO_8859_15,
+            encoding_rs::WINDOWS_1250,
+            encoding_rs::ISO_8859_2,
+            encoding_rs::WINDOWS_1251,
+            encoding_rs::KOI8_R,
+            encoding_rs::ISO_8859_5,
+            encoding_rs::WINDOWS_1253,
+            encoding_rs::ISO_8859_7,
+            encoding_rs::WINDOWS_1254,
+            encoding_rs::WINDOWS_1255,
+            encoding_rs::ISO_8859_8,
+            encoding_rs::WINDOWS_1256,
+            encoding_rs::ISO_8859_6,
+            encoding_rs::WINDOWS_1257,
+            encoding_rs::ISO_8859_13,
+            encoding_rs::WINDOWS_1258,
+            encoding_rs::ISO_8859_3,
+            encoding_rs::ISO_8859_4,
+            encoding_rs::ISO_8859_10,
+            encoding_rs::ISO_8859_14,
+            encoding_rs::ISO_8859_16,
+            encoding_rs::SHIFT_JIS,
+            encoding_rs::GB18030,
+        ];
+
+        unicode
+            .into_iter()
+            .chain(legacy.into_iter().map(Charset::Legacy))
+            .collect()
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Charset::Utf8 => "UTF-8",
+            Charset::Utf16Le => "UTF-16LE",
+            Charset::Utf16Be => "UTF-16BE",
+            Charset::Utf32Le => "UTF-32LE",
+            Charset::Utf32Be => "UTF-32BE",
+            Charset::Latin1 => "ISO-8859-1",
+            Charset::Legacy(encoding) => encoding.name(),
+        }
+    }
+
+    /// The byte order mark of Unicode encodings and GB18030.
+    pub fn bom(self) -> Option<Bom> {
+        match self {
+            Charset::Utf8 => Some(Bom::Utf8),
+            Charset::Utf16Le => Some(Bom::Utf16Le),
+            Charset::Utf16Be => Some(Bom::Utf16Be),
+            Charset::Utf32Le => Some(Bom::Utf32Le),
+            Charset::Utf32Be => Some(Bom::Utf32Be),
+            Charset::Legacy(encoding) if encoding == encoding_rs::GB18030 => Some(Bom::Gb18030),
+            _ => None,
+        }
+    }
+
+    /// Decode `bytes`, without a byte order mark. `None` if they are not valid in the charset.
+    pub fn decode(self, bytes: &[u8]) -> Option<String> {
+        match self {
+            Charset::Utf8 => String::from_utf8(bytes.to_vec()).ok(),
+            Charset::Utf16Le | Charset::Utf16Be => {
+                let chunks = bytes.chunks_exact(2);
+                if !chunks.remainder().is_empty() {
+                    return None;
+                }
+                let units = chunks.map(|c| match self {
+                    Charset::Utf16Le => u16::from_le_bytes([c[0], c[1]]),
+                    _ => u16::from_be_bytes([c[0], c[1]]),
+                });
+                char::decode_utf16(units).collect::<Result<_, _>>().ok()
+            }
+            Charset::Utf32Le | Charset::Utf32Be => {
+                let chunks = bytes.chunks_exact(4);
+                if !chunks.remainder().is_empty() {
+                    return None;
+                }
+                chunks
+                    .map(|c| {
+                        let c = [c[0], c[1], c[2], c[3]];
+                        char::from_u32(match self {
+                            Charset::Utf32Le => u32::from_le_bytes(c),
+                            _ => u32::from_be_bytes(c),
+                        })
+                    })
+                    .collect()
+            }
+            Charset::Latin1 => Some(bytes.iter().map(|&b| char::from(b)).collect()),
+            Charset::Legacy(encoding) => encoding
+                .decode_without_bom_handling_and_without_replacement(bytes)
+                .map(|text| text.into_owned()),
+        }
+    }
+}
+
+impl fmt::Display for Charset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+    Hebrew,
+    Arabic,
+    /// Han and kana, which Japanese mixes within words.
+    Cjk,
+    Hangul,
+    Other,
+}
+
+/// The share of a class of characters in typical text, its number of characters and its script.
+fn class(c: char) -> (f64, f64, Script) {
+    match c {
+        'a'..='z' => (0.45, 26.0, Script::Latin),
+        'A'..='Z' => (0.04, 26.0, Script::Latin),
+        ' '..='~' => (0.2, 43.0, Script::Other),
+        '\t' | '\n' | '\r' => (0.02, 3.0, Script::Other),
+        'ß'..='ÿ' if c != '÷' => (0.02, 32.0, Script::Latin),
+        'À'..='Þ' if c != '×' => (0.005, 30.0, Script::Latin),
+        '\u{A0}'..='\u{BF}' | '×' | '÷' => (0.002, 34.0, Script::Other),
+        '\u{100}'..='\u{24F}' | '\u{1E00}'..='\u{1EFF}' => (0.02, 592.0, Script::Latin),
+        '\u{370}'..='\u{3FF}' => (0.02, 144.0, Script::Greek),
+        '\u{430}'..='\u{45F}' => (0.045, 48.0, Script::Cyrillic),
+        '\u{400}'..='\u{4FF}' => (0.005, 208.0, Script::Cyrillic),
+        '\u{590}'..='\u{5FF}' => (0.01, 112.0, Script::Hebrew),
+        '\u{600}'..='\u{6FF}' => (0.01, 256.0, Script::Arabic),
+        '\u{2000}'..='\u{206F}' | '\u{20A0}'..='\u{20CF}' | '\u{2100}'..='\u{214F}' => {
+            (0.01, 240.0, Script::Other)
+        }
+        '\u{3000}'..='\u{303F}' | '\u{FF00}'..='\u{FFEF}' => (0.01, 304.0, Script::Cjk),
+        '\u{3040}'..='\u{30FF}' => (0.04, 192.0, Script::Cjk),
+        '\u{4E00}'..='\u{9FFF}' => (0.06, 20992.0, Script::Cjk),
+        '\u{AC00}'..='\u{D7A3}' => (0.02, 11172.0, Script::Hangul),
+        c if c.is_control() => (1e-6, 62.0, Script::Other),
+        _ => (0.002, 1_000_000.0, Script::Other),
+    }
+}
+
+// The odds of a word switching scripts, or of an uppercase letter following a lowercase one
+const SCRIPT_SWITCH_ODDS: f64 = 0.01;
+const CASE_SWITCH_ODDS: f64 = 0.1;
+
+/// The log-likelihood of `text` under the model of typical text.
+pub(crate) fn score(text: &str) -> f64 {
+    let mut score = 0.0;
+    let mut previous: Option<(char, Script)> = None;
+
+    for c in text.chars() {
+        let (share, size, script) = class(c);
+        score += (share / size).ln();
+
+        if let Some((p, previous_script)) = previous
+            && p.is_alphabetic()
+            && c.is_alphabetic()
+        {
+            if previous_script != script {
+                score += SCRIPT_SWITCH_ODDS.ln();
+            }
+            if p.is_lowercase() && c.is_uppercase() {
+                score += CASE_SWITCH_ODDS.ln();
+            }
+        }
+        previous = Some((c, script));
+    }
+
+    score
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Debug)]
+pub struct Candidate {
+    pub charset: Charset,
+    /// Whether the bytes start with the byte order mark of the charset, left out of `text`.
+    pub bom: bool,
+    pub text: String,
+    /// The probability of the charset among all candidates, between 0 and 1.
+    pub confidence: f64,
+}
+
+// The odds of bytes starting like a byte order mark by chance
+const BOM_ODDS: f64 = 1e-4;
+
+// The odds of each charset being used, as it is further down `Charset::all`
+const CHARSET_DECAY: f64 = 0.9;
+
+/// The charsets `bytes` are valid in, ranked by confidence.
+pub fn detect(bytes: &[u8]) -> Vec<Candidate> {
+    let mut candidates = vec![];
+
+    for (i, charset) in Charset::all().into_iter().enumerate() {
+        let prior = CHARSET_DECAY.ln() * i as f64;
+
+        let bom = charset.bom().filter(|bom| bytes.starts_with(bom.bytes()));
+        let Some(text) = charset.decode(&bytes[bom.map_or(0, |bom| bom.bytes().len())..]) else {
+            continue;
+        };
+
+        // NOTE: a byte order mark is evidence of its charset, as it is unlikely to occur by chance
+        let mut likelihood = score(&text) + prior;
+        if bom.is_some() {
+            likelihood -= BOM_ODDS.ln();
+        }
+
+        candidates.push(((charset, bom.is_some(), text), likelihood));
+    }
+
+    rank_by_odds(candidates)
+        .into_iter()
+        .map(|((charset, bom, text), confidence)| Candidate {
+            charset,
+            bom,
+            text,
+            confidence,
+        })
+        .collect()
+}
+
+/// The text of `bytes` in its most likely charset.
+pub fn decode(bytes: &[u8]) -> Option<String> {
+    detect(bytes).into_iter().next().map(|c| c.text)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode, detect, Charset};
+
+    fn best(bytes: &[u8]) -> (String, String) {
+        let best = detect(bytes).into_iter().next().unwrap();
+        (best.charset.name().to_string(), best.text)
+    }
+
+    #[test]
+    fn detects_unicode() {
+        assert_eq!(
+            best("anwähle".as_bytes()),
+            ("UTF-8".into(), "anwähle".into())
+        );
+        assert_eq!(
+            best(b"\x00p\x00o\x00\xeb\x00e\x00m"),
+            ("UTF-16BE".into(), "poëem".into())
+        );
+
+        let utf32: Vec<u8> = "\u{FEFF}größe"
+            .chars()
+            .flat_map(|c| (c as u32).to_le_bytes())
+            .collect();
+        let candidates = detect(&utf32);
+        assert_eq!(candidates[0].charset, Charset::Utf32Le);
+        assert!(candidates[0].bom);
+        assert_eq!(candidates[0].text, "größe");
+        assert!(candidates[0].confidence > 0.99);
+    }
+
+    #[test]
+    fn detects_legacy_charsets() {
+        let text = "yltämässä";
+        let (encoded, _, _) = encoding_rs::WINDOWS_1252.encode(text);
+        assert_eq!(decode(&encoded).as_deref(), Some(text));
+
+        let text = "привет, как дела?";
+        for encoding in [encoding_rs::KOI8_R, encoding_rs::WINDOWS_1251] {
+            let (encoded, _, _) = encoding.encode(text);
+            assert_eq!(best(&encoded), (encoding.name().into(), text.into()));
+        }
+
+        let text = "こんにちは、世界";
+        let (encoded, _, _) = encoding_rs::SHIFT_JIS.encode(text);
+        assert_eq!(best(&encoded), ("Shift_JIS".into(), text.into()));
+
+        let text = "你好，世界";
+        let (encoded, _, _) = encoding_rs::GB18030.encode(text);
+        assert_eq!(best(&encoded), ("gb18030".into(), text.into()));
+    }
+}
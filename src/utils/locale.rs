@@ -0,0 +1,770 @@
+/// Names and patterns of dates in a few locales, as in the Gregorian calendar of CLDR 46.
+///
+/// Month and weekday names, day periods, eras and the patterns of dates and times are copied from
+/// the `cldr-dates-full` data of that release, names in their `wide` and `abbreviated` widths. The
+/// layouts used to parse dates also accept common writings CLDR does not list, e.g. `1st`.
+use chrono::Weekday;
+
+/// Locales with the names and layouts used in dates, following CLDR.
+///
+/// Month names are those of the format context, e.g. the Polish genitive `stycznia` in
+/// `5 stycznia 2024`, and weekday names those starting a full date, e.g. the Finnish `tiistai`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Locale {
+    English,
+    French,
+    German,
+    Spanish,
+    Italian,
+    Portuguese,
+    Dutch,
+    Swedish,
+    Danish,
+    Polish,
+    Finnish,
+    Turkish,
+    Japanese,
+}
+
+type MonthNames = [&'static str; 12];
+type WeekdayNames = [&'static str; 7];
+
+const JAPANESE_MONTHS: MonthNames = [
+    "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+];
+
+impl Locale {
+    pub const ALL: [Locale; 13] = [
+        Locale::English,
+        Locale::French,
+        Locale::German,
+        Locale::Spanish,
+        Locale::Italian,
+        Locale::Portuguese,
+        Locale::Dutch,
+        Locale::Swedish,
+        Locale::Danish,
+        Locale::Polish,
+        Locale::Finnish,
+        Locale::Turkish,
+        Locale::Japanese,
+    ];
+
+    /// The ISO 639-1 code of the language, e.g. `fr`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::French => "fr",
+            Locale::German => "de",
+            Locale::Spanish => "es",
+            Locale::Italian => "it",
+            Locale::Portuguese => "pt",
+            Locale::Dutch => "nl",
+            Locale::Swedish => "sv",
+            Locale::Danish => "da",
+            Locale::Polish => "pl",
+            Locale::Finnish => "fi",
+            Locale::Turkish => "tr",
+            Locale::Japanese => "ja",
+        }
+    }
+
+    /// The locale of a language code, ignoring any region, e.g. `fr` or `fr-CA`.
+    pub fn from_code(code: &str) -> Option<Locale> {
+        let language = code.split(['-', '_']).next()?;
+        Locale::ALL
+            .into_iter()
+            .find(|l| l.code().eq_ignore_ascii_case(language))
+    }
+
+    pub fn months(self) -> &'static MonthNames {
+        match self {
+            Locale::English => &[
+                "January",
+                "February",
+                "March",
+                "April",
+                "May",
+                "June",
+                "July",
+                "August",
+                "September",
+                "October",
+                "November",
+                "December",
+            ],
+            Locale::French => &[
+                "janvier",
+                "février",
+                "mars",
+                "avril",
+                "mai",
+                "juin",
+                "juillet",
+                "août",
+                "septembre",
+                "octobre",
+                "novembre",
+                "décembre",
+            ],
+            Locale::German => &[
+                "Januar",
+                "Februar",
+                "März",
+                "April",
+                "Mai",
+                "Juni",
+                "Juli",
+                "August",
+                "September",
+                "Oktober",
+                "November",
+                "Dezember",
+            ],
+            Locale::Spanish => &[
+                "enero",
+                "febrero",
+                "marzo",
+                "abril",
+                "mayo",
+                "junio",
+                "julio",
+                "agosto",
+                "septiembre",
+                "octubre",
+                "noviembre",
+                "diciembre",
+            ],
+            Locale::Italian => &[
+                "gennaio",
+                "febbraio",
+                "marzo",
+                "aprile",
+                "maggio",
+                "giugno",
+                "luglio",
+                "agosto",
+                "settembre",
+                "ottobre",
+                "novembre",
+                "dicembre",
+            ],
+            Locale::Portuguese => &[
+                "janeiro",
+                "fevereiro",
+                "março",
+                "abril",
+                "maio",
+                "junho",
+                "julho",
+                "agosto",
+                "setembro",
+                "outubro",
+                "novembro",
+                "dezembro",
+            ],
+            Locale::Dutch => &[
+                "januari",
+                "februari",
+                "maart",
+                "april",
+                "mei",
+                "juni",
+                "juli",
+                "augustus",
+                "september",
+                "oktober",
+                "november",
+                "december",
+            ],
+            Locale::Swedish => &[
+                "januari",
+                "februari",
+                "mars",
+                "april",
+                "maj",
+                "juni",
+                "juli",
+                "augusti",
+                "september",
+                "oktober",
+                "november",
+                "december",
+            ],
+            Locale::Danish => &[
+                "januar",
+                "februar",
+                "marts",
+                "april",
+                "maj",
+                "juni",
+                "juli",
+                "august",
+                "september",
+                "oktober",
+                "november",
+                "december",
+            ],
+            Locale::Polish => &[
+                "stycznia",
+                "lutego",
+                "marca",
+                "kwietnia",
+                "maja",
+                "czerwca",
+                "lipca",
+                "sierpnia",
+                "września",
+                "października",
+                "listopada",
+                "grudnia",
+            ],
+            Locale::Finnish => &[
+                "tammikuuta",
+                "helmikuuta",
+                "maaliskuuta",
+                "huhtikuuta",
+                "toukokuuta",
+                "kesäkuuta",
+                "heinäkuuta",
+                "elokuuta",
+                "syyskuuta",
+                "lokakuuta",
+                "marraskuuta",
+                "joulukuuta",
+            ],
+            Locale::Turkish => &[
+                "Ocak", "Şubat", "Mart", "Nisan", "Mayıs", "Haziran", "Temmuz", "Ağustos", "Eylül",
+                "Ekim", "Kasım", "Aralık",
+            ],
+            Locale::Japanese => &JAPANESE_MONTHS,
+        }
+    }
+
+    /// Abbreviated month names, with their trailing period if any.
+    pub fn months_abbreviated(self) -> &'static MonthNames {
+        match self {
+            Locale::English => &[
+                "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+            ],
+            Locale::French => &[
+                "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+                "nov.", "déc.",
+            ],
+            Locale::German => &[
+                "Jan.", "Feb.", "März", "Apr.", "Mai", "Juni", "Juli", "Aug.", "Sept.", "Okt.",
+                "Nov.", "Dez.",
+            ],
+            Locale::Spanish => &[
+                "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+            ],
+            Locale::Italian => &[
+                "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+            ],
+            Locale::Portuguese => &[
+                "jan.", "fev.", "mar.", "abr.", "mai.", "jun.", "jul.", "ago.", "set.", "out.",
+                "nov.", "dez.",
+            ],
+            Locale::Dutch => &[
+                "jan", "feb", "mrt", "apr", "mei", "jun", "jul", "aug", "sep", "okt", "nov", "dec",
+            ],
+            Locale::Swedish => &[
+                "jan.", "feb.", "mars", "apr.", "maj", "juni", "juli", "aug.", "sep.", "okt.",
+                "nov.", "dec.",
+            ],
+            Locale::Danish => &[
+                "jan.", "feb.", "mar.", "apr.", "maj", "jun.", "jul.", "aug.", "sep.", "okt.",
+                "nov.", "dec.",
+            ],
+            Locale::Polish => &[
+                "sty", "lut", "mar", "kwi", "maj", "cze", "lip", "sie", "wrz", "paź", "lis", "gru",
+            ],
+            Locale::Finnish => &[
+                "tammik.", "helmik.", "maalisk.", "huhtik.", "toukok.", "kesäk.", "heinäk.",
+                "elok.", "syysk.", "lokak.", "marrask.", "jouluk.",
+            ],
+            Locale::Turkish => &[
+                "Oca", "Şub", "Mar", "Nis", "May", "Haz", "Tem", "Ağu", "Eyl", "Eki", "Kas", "Ara",
+            ],
+            Locale::Japanese => &JAPANESE_MONTHS,
+        }
+    }
+
+    /// Weekday names, from Monday to Sunday.
+    pub fn weekdays(self) -> &'static WeekdayNames {
+        match self {
+            Locale::English => &[
+                "Monday",
+                "Tuesday",
+                "Wednesday",
+                "Thursday",
+                "Friday",
+                "Saturday",
+                "Sunday",
+            ],
+            Locale::French => &[
+                "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+            ],
+            Locale::German => &[
+                "Montag",
+                "Dienstag",
+                "Mittwoch",
+                "Donnerstag",
+                "Freitag",
+                "Samstag",
+                "Sonntag",
+            ],
+            Locale::Spanish => &[
+                "lunes",
+                "martes",
+                "miércoles",
+                "jueves",
+                "viernes",
+                "sábado",
+                "domingo",
+            ],
+            Locale::Italian => &[
+                "lunedì",
+                "martedì",
+                "mercoledì",
+                "giovedì",
+                "venerdì",
+                "sabato",
+                "domenica",
+            ],
+            Locale::Portuguese => &[
+                "segunda-feira",
+                "terça-feira",
+                "quarta-feira",
+                "quinta-feira",
+                "sexta-feira",
+                "sábado",
+                "domingo",
+            ],
+            Locale::Dutch => &[
+                "maandag",
+                "dinsdag",
+                "woensdag",
+                "donderdag",
+                "vrijdag",
+                "zaterdag",
+                "zondag",
+            ],
+            Locale::Swedish => &[
+                "måndag", "tisdag", "onsdag", "torsdag", "fredag", "lördag", "söndag",
+            ],
+            Locale::Danish => &[
+                "mandag", "tirsdag", "onsdag", "torsdag", "fredag", "lørdag", "søndag",
+            ],
+            Locale::Polish => &[
+                "poniedziałek",
+                "wtorek",
+                "środa",
+                "czwartek",
+                "piątek",
+                "sobota",
+                "niedziela",
+            ],
+            Locale::Finnish => &[
+                "maanantai",
+                "tiistai",
+                "keskiviikko",
+                "torstai",
+                "perjantai",
+                "lauantai",
+                "sunnuntai",
+            ],
+            Locale::Turkish => &[
+                "Pazartesi",
+                "Salı",
+                "Çarşamba",
+                "Perşembe",
+                "Cuma",
+                "Cumartesi",
+                "Pazar",
+            ],
+            Locale::Japanese => &[
+                "月曜日",
+                "火曜日",
+                "水曜日",
+                "木曜日",
+                "金曜日",
+                "土曜日",
+                "日曜日",
+            ],
+        }
+    }
+
+    /// Abbreviated weekday names, from Monday to Sunday, with their trailing period if any.
+    pub fn weekdays_abbreviated(self) -> &'static WeekdayNames {
+        match self {
+            Locale::English => &["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+            Locale::French => &["lun.", "mar.", "mer.", "jeu.", "ven.", "sam.", "dim."],
+            Locale::German => &["Mo.", "Di.", "Mi.", "Do.", "Fr.", "Sa.", "So."],
+            Locale::Spanish => &["lun", "mar", "mié", "jue", "vie", "sáb", "dom"],
+            Locale::Italian => &["lun", "mar", "mer", "gio", "ven", "sab", "dom"],
+            Locale::Portuguese => &["seg.", "ter.", "qua.", "qui.", "sex.", "sáb.", "dom."],
+            Locale::Dutch => &["ma", "di", "wo", "do", "vr", "za", "zo"],
+            Locale::Swedish => &["mån", "tis", "ons", "tors", "fre", "lör", "sön"],
+            Locale::Danish => &["man.", "tirs.", "ons.", "tors.", "fre.", "lør.", "søn."],
+            Locale::Polish => &["pon.", "wt.", "śr.", "czw.", "pt.", "sob.", "niedz."],
+            Locale::Finnish => &["ma", "ti", "ke", "to", "pe", "la", "su"],
+            Locale::Turkish => &["Pzt", "Sal", "Çar", "Per", "Cum", "Cmt", "Paz"],
+            Locale::Japanese => &["月", "火", "水", "木", "金", "土", "日"],
+        }
+    }
+
+    /// Layouts of dates, in CLDR's pattern syntax: `d` is the day, `M` the month number, `MMMM`
+    /// its name, `EEEE` the name of the weekday, `y` the year and text is quoted.
+    pub fn date_layouts(self) -> &'static [&'static str] {
+        match self {
+            Locale::English => &[
+                "MMMM d, y",
+                "EEEE, MMMM d, y",
+                "d MMMM y",
+                "EEEE d MMMM y",
+                "M/d/y",
+            ],
+            Locale::French => &["d MMMM y", "EEEE d MMMM y", "d/M/y"],
+            Locale::German => &["d. MMMM y", "EEEE, d. MMMM y", "d.M.y"],
+            Locale::Spanish => &[
+                "d 'de' MMMM 'de' y",
+                "EEEE, d 'de' MMMM 'de' y",
+                "d MMMM y",
+                "d/M/y",
+            ],
+            Locale::Italian => &["d MMMM y", "EEEE d MMMM y", "d/M/y"],
+            Locale::Portuguese => &["d 'de' MMMM 'de' y", "EEEE, d 'de' MMMM 'de' y", "d/M/y"],
+            Locale::Dutch => &["d MMMM y", "EEEE d MMMM y", "d-M-y"],
+            Locale::Swedish => &["d MMMM y", "EEEE d MMMM y", "y-MM-dd"],
+            Locale::Danish => &["d. MMMM y", "EEEE 'den' d. MMMM y", "d.M.y"],
+            Locale::Polish => &["d MMMM y", "EEEE, d MMMM y", "d.MM.y"],
+            Locale::Finnish => &["d. MMMM y", "EEEE d. MMMM y", "d.M.y"],
+            Locale::Turkish => &["d MMMM y", "d MMMM y EEEE", "d.MM.y"],
+            Locale::Japanese => &["y年M月d日", "y年M月d日EEEE", "y年M月d日(EEEE)", "y/MM/dd"],
+        }
+    }
+
+    /// Layouts of times, in CLDR's pattern syntax: `H` is the hour from 0 to 23, `h` from 1 to
+    /// 12 with `a` for [`Locale::day_periods`], `mm` the minutes and `ss` the seconds.
+    pub fn time_layouts(self) -> &'static [&'static str] {
+        match self {
+            Locale::English => &["H:mm", "H:mm:ss", "h:mm a", "h:mm:ss a", "h a"],
+            Locale::French => &["H:mm", "H:mm:ss", "H 'h' mm"],
+            Locale::German => &["H:mm 'Uhr'", "H:mm", "H:mm:ss"],
+            Locale::Finnish | Locale::Danish => &["H.mm", "H.mm.ss", "H:mm"],
+            Locale::Japanese => &["H:mm", "H:mm:ss", "H時mm分", "ah:mm", "ah時mm分"],
+            _ => &["H:mm", "H:mm:ss"],
+        }
+    }
+
+    /// What may come between a date and its time, besides spaces.
+    pub fn time_connectors(self) -> &'static [&'static str] {
+        match self {
+            Locale::English => &[",", "at"],
+            Locale::French => &[",", "à"],
+            Locale::German => &[",", "um"],
+            Locale::Spanish => &[",", "a las", "a la"],
+            Locale::Italian => &[",", "alle", "ore"],
+            Locale::Portuguese => &[",", "às"],
+            Locale::Dutch => &[",", "om"],
+            Locale::Swedish | Locale::Danish => &[",", "kl."],
+            Locale::Polish => &[",", "o"],
+            Locale::Finnish => &[",", "klo"],
+            Locale::Turkish | Locale::Japanese => &[","],
+        }
+    }
+
+    /// The markers of the morning and afternoon for 12-hour times.
+    pub fn day_periods(self) -> [&'static str; 2] {
+        match self {
+            Locale::Japanese => ["午前", "午後"],
+            _ => ["AM", "PM"],
+        }
+    }
+
+    /// What may follow a day number, e.g. `1st` or `1er`.
+    pub fn ordinal_suffixes(self) -> &'static [&'static str] {
+        match self {
+            Locale::English => &["st", "nd", "rd", "th"],
+            Locale::French => &["er"],
+            Locale::Spanish | Locale::Portuguese => &["º"],
+            _ => &[],
+        }
+    }
+
+    /// CLDR's patterns of dates of each [`Style`].
+    pub fn date_pattern(self, style: Style) -> &'static str {
+        let [short, medium, long, full] = match self {
+            Locale::English => ["M/d/yy", "MMM d, y", "MMMM d, y", "EEEE, MMMM d, y"],
+            Locale::French => ["dd/MM/y", "d MMM y", "d MMMM y", "EEEE d MMMM y"],
+            Locale::German => ["dd.MM.yy", "dd.MM.y", "d. MMMM y", "EEEE, d. MMMM y"],
+            Locale::Spanish => [
+                "d/M/yy",
+                "d MMM y",
+                "d 'de' MMMM 'de' y",
+                "EEEE, d 'de' MMMM 'de' y",
+            ],
+            Locale::Italian => ["dd/MM/yy", "d MMM y", "d MMMM y", "EEEE d MMMM y"],
+            Locale::Portuguese => [
+                "dd/MM/y",
+                "d 'de' MMM 'de' y",
+                "d 'de' MMMM 'de' y",
+                "EEEE, d 'de' MMMM 'de' y",
+            ],
+            Locale::Dutch => ["dd-MM-y", "d MMM y", "d MMMM y", "EEEE d MMMM y"],
+            Locale::Swedish => ["y-MM-dd", "d MMM y", "d MMMM y", "EEEE d MMMM y"],
+            Locale::Danish => ["dd.MM.y", "d. MMM y", "d. MMMM y", "EEEE 'den' d. MMMM y"],
+            Locale::Polish => ["d.MM.y", "d MMM y", "d MMMM y", "EEEE, d MMMM y"],
+            Locale::Finnish => ["d.M.y", "d.M.y", "d. MMMM y", "EEEE d. MMMM y"],
+            Locale::Turkish => ["d.MM.y", "d MMM y", "d MMMM y", "d MMMM y EEEE"],
+            Locale::Japanese => ["y/MM/dd", "y/MM/dd", "y年M月d日", "y年M月d日EEEE"],
+        };
+
+        match style {
+            Style::Short => short,
+            Style::Medium => medium,
+            Style::Long => long,
+            Style::Full => full,
+        }
+    }
+
+    /// CLDR's patterns of times of each [`Style`], in the [`Locale::hour_cycle`] of the locale.
+    pub fn time_pattern(self, style: Style) -> &'static str {
+        let [short, medium, long, full] = match self {
+            Locale::English => ["h:mm a", "h:mm:ss a", "h:mm:ss a z", "h:mm:ss a zzzz"],
+            Locale::Finnish => ["H.mm", "H.mm.ss", "H.mm.ss z", "H.mm.ss zzzz"],
+            Locale::Danish => ["HH.mm", "HH.mm.ss", "HH.mm.ss z", "HH.mm.ss zzzz"],
+            Locale::Japanese => ["H:mm", "H:mm:ss", "H:mm:ss z", "H時mm分ss秒 zzzz"],
+            _ => ["HH:mm", "HH:mm:ss", "HH:mm:ss z", "HH:mm:ss zzzz"],
+        };
+
+        match style {
+            Style::Short => short,
+            Style::Medium => medium,
+            Style::Long => long,
+            Style::Full => full,
+        }
+    }
+
+    /// CLDR's pattern joining the date `{1}` and the time `{0}`, by the [`Style`] of the date.
+    pub fn date_time_pattern(self, style: Style) -> &'static str {
+        let [short, long] = match self {
+            Locale::English => ["{1}, {0}", "{1} 'at' {0}"],
+            Locale::French => ["{1} {0}", "{1} 'à' {0}"],
+            Locale::German => ["{1}, {0}", "{1} 'um' {0}"],
+            Locale::Portuguese => ["{1} {0}", "{1} 'às' {0}"],
+            Locale::Dutch => ["{1} {0}", "{1} 'om' {0}"],
+            Locale::Danish => ["{1} {0}", "{1} 'kl'. {0}"],
+            Locale::Finnish => ["{1} {0}", "{1} 'klo' {0}"],
+            Locale::Spanish | Locale::Polish => ["{1}, {0}", "{1}, {0}"],
+            Locale::Italian => ["{1}, {0}", "{1} {0}"],
+            Locale::Swedish | Locale::Turkish | Locale::Japanese => ["{1} {0}", "{1} {0}"],
+        };
+
+        match style {
+            Style::Short | Style::Medium => short,
+            Style::Long | Style::Full => long,
+        }
+    }
+
+    /// Whether the locale prefers 12 or 24-hour times.
+    pub fn hour_cycle(self) -> HourCycle {
+        match self {
+            Locale::English => HourCycle::H12,
+            _ => HourCycle::H23,
+        }
+    }
+
+    /// The abbreviated names of the eras before and after the birth of Christ.
+    pub fn eras(self) -> [&'static str; 2] {
+        match self {
+            Locale::English => ["BC", "AD"],
+            Locale::French => ["av. J.-C.", "ap. J.-C."],
+            Locale::German => ["v. Chr.", "n. Chr."],
+            Locale::Spanish => ["a. C.", "d. C."],
+            Locale::Italian | Locale::Portuguese => ["a.C.", "d.C."],
+            Locale::Dutch => ["v.Chr.", "n.Chr."],
+            Locale::Swedish | Locale::Danish => ["f.Kr.", "e.Kr."],
+            Locale::Polish => ["p.n.e.", "n.e."],
+            Locale::Finnish => ["eKr.", "jKr."],
+            Locale::Turkish => ["MÖ", "MS"],
+            Locale::Japanese => ["紀元前", "西暦"],
+        }
+    }
+
+    /// CLDR's format of UTC offsets `{0}`, e.g. `GMT+1`.
+    pub fn gmt_format(self) -> &'static str {
+        match self {
+            Locale::French | Locale::Finnish => "UTC{0}",
+            _ => "GMT{0}",
+        }
+    }
+
+    /// CLDR's format of the name of the time zone of a place `{0}`, e.g. `Paris Time`.
+    pub fn region_format(self) -> &'static str {
+        match self {
+            Locale::English => "{0} Time",
+            Locale::French => "heure : {0}",
+            Locale::German => "{0} (Ortszeit)",
+            Locale::Spanish => "hora de {0}",
+            Locale::Italian => "Ora {0}",
+            Locale::Portuguese => "Horário {0}",
+            Locale::Dutch => "{0}-tijd",
+            Locale::Swedish => "{0}tid",
+            Locale::Danish => "{0}-tid",
+            Locale::Polish => "czas: {0}",
+            Locale::Finnish => "aikavyöhyke: {0}",
+            Locale::Turkish => "{0} Saati",
+            Locale::Japanese => "{0}時間",
+        }
+    }
+
+    /// The month (1 to 12) of a full or abbreviated name, ignoring case and trailing periods.
+    pub fn parse_month(self, name: &str) -> Option<u32> {
+        let name = normalize(name);
+        (1..=12).find(|&m| {
+            let i = m as usize - 1;
+            normalize(self.months()[i]) == name || normalize(self.months_abbreviated()[i]) == name
+        })
+    }
+
+    /// The weekday of a full or abbreviated name, ignoring case and trailing periods.
+    pub fn parse_weekday(self, name: &str) -> Option<Weekday> {
+        let name = normalize(name);
+        let i = (0..7).find(|&i| {
+            normalize(self.weekdays()[i]) == name
+                || normalize(self.weekdays_abbreviated()[i]) == name
+        })?;
+
+        Weekday::try_from(i as u8).ok()
+    }
+}
+
+fn normalize(name: &str) -> String {
+    name.trim_end_matches('.').to_lowercase()
+}
+
+/// The length of a date or time pattern, from numeric dates to dates with weekday names.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Style {
+    Short,
+    Medium,
+    Long,
+    Full,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HourCycle {
+    /// From 1 to 12 with AM and PM.
+    H12,
+    /// From 0 to 23.
+    H23,
+}
+
+/// A part of a CLDR pattern: a field letter repeated a number of times, or literal text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PatternItem {
+    Field(char, usize),
+    Literal(String),
+}
+
+/// Split a CLDR pattern into fields and literal text. ASCII letters are fields unless quoted, and
+/// `''` is a quote.
+pub fn parse_pattern(pattern: &str) -> Vec<PatternItem> {
+    let mut items = vec![];
+    let mut literal = String::new();
+    let mut chars = pattern.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\'' {
+            // `''` is a quote, in quoted text or not
+            if chars.next_if_eq(&'\'').is_some() {
+                literal.push('\'');
+                continue;
+            }
+            while let Some(c) = chars.next() {
+                match c {
+                    '\'' if chars.next_if_eq(&'\'').is_some() => literal.push('\''),
+                    '\'' => break,
+                    c => literal.push(c),
+                }
+            }
+        } else if c.is_ascii_alphabetic() {
+            if !literal.is_empty() {
+                items.push(PatternItem::Literal(std::mem::take(&mut literal)));
+            }
+            let mut count = 1;
+            while chars.next_if_eq(&c).is_some() {
+                count += 1;
+            }
+            items.push(PatternItem::Field(c, count));
+        } else {
+            literal.push(c);
+        }
+    }
+
+    if !literal.is_empty() {
+        items.push(PatternItem::Literal(literal));
+    }
+
+    items
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use chrono::Weekday;
+
+    use super::{parse_pattern, Locale, PatternItem};
+
+    #[test]
+    fn parses_month_names() {
+        assert_eq!(Locale::French.parse_month("Février"), Some(2));
+        assert_eq!(Locale::French.parse_month("févr"), Some(2));
+        assert_eq!(Locale::German.parse_month("MÄRZ"), Some(3));
+        assert_eq!(Locale::Polish.parse_month("września"), Some(9));
+        assert_eq!(Locale::Turkish.parse_month("şubat"), Some(2));
+        assert_eq!(Locale::English.parse_month("Sept"), None);
+        assert_eq!(Locale::Japanese.parse_month("12月"), Some(12));
+        assert_eq!(Locale::from_code("pt-BR"), Some(Locale::Portuguese));
+    }
+
+    #[test]
+    fn parses_weekday_names() {
+        assert_eq!(Locale::German.parse_weekday("Di."), Some(Weekday::Tue));
+        assert_eq!(
+            Locale::Portuguese.parse_weekday("Sábado"),
+            Some(Weekday::Sat)
+        );
+        assert_eq!(Locale::Japanese.parse_weekday("日曜日"), Some(Weekday::Sun));
+        assert_eq!(
+            Locale::Turkish.parse_weekday("cumartesi"),
+            Some(Weekday::Sat)
+        );
+    }
+
+    #[test]
+    fn parses_patterns() {
+        assert_eq!(
+            parse_pattern("EEEE 'den' d. MMMM y"),
+            [
+                PatternItem::Field('E', 4),
+                PatternItem::Literal(" den ".to_string()),
+                PatternItem::Field('d', 1),
+                PatternItem::Literal(". ".to_string()),
+                PatternItem::Field('M', 4),
+                PatternItem::Literal(" ".to_string()),
+                PatternItem::Field('y', 1),
+            ]
+        );
+        assert_eq!(
+            parse_pattern("h 'o''clock'"),
+            [
+                PatternItem::Field('h', 1),
+                PatternItem::Literal(" o'clock".to_string()),
+            ]
+        );
+    }
+}
@@ -50,3 +50,21 @@ pub fn find_repeating_sequence<T: PartialEq>(data: &[T]) -> Option<(usize, usize
 
     None
 }
+
+/// Turn the natural log odds of `candidates` into probabilities summing to 1, most likely first.
+pub fn rank_by_odds<T>(candidates: Vec<(T, f64)>) -> Vec<(T, f64)> {
+    // Relative to the best odds, so that the exponentials don't underflow to 0
+    let best = candidates
+        .iter()
+        .map(|(_, odds)| *odds)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let total: f64 = candidates.iter().map(|(_, odds)| (odds - best).exp()).sum();
+
+    let mut ranked: Vec<(T, f64)> = candidates
+        .into_iter()
+        .map(|(candidate, odds)| (candidate, (odds - best).exp() / total))
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    ranked
+}
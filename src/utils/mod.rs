@@ -1,8 +1,20 @@
+pub mod bom;
+pub mod calendar;
+pub mod dateformat;
+pub mod dateorder;
+pub mod encoding;
 pub mod grid2d;
 pub mod grid3d;
+pub mod locale;
 pub mod misc;
+pub mod mojibake;
 pub mod parse;
 pub mod range;
 pub mod time;
+pub mod tzdata;
+pub mod tzdb;
+pub mod tzif;
+pub mod utf16;
+pub mod utf8;
 pub mod vector2d;
 pub mod vector3d;
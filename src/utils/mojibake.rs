@@ -0,0 +1,218 @@
+/// Repair of mojibake, text whose UTF-8 bytes were decoded with a legacy single-byte codec,
+/// possibly several times over.
+///
+/// A layer is undone by encoding the text back with its codec and decoding the bytes as UTF-8.
+/// [`unmojibake`] peels layers as long as the text gets more likely, as scored by the model of
+/// [`crate::utils::encoding`], e.g. `Ã©` is `é` read as Latin-1.
+use std::fmt;
+
+use crate::utils::encoding::score;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Codec {
+    Latin1,
+    Windows1252,
+    MacRoman,
+    /// The code page of the IBM PC, e.g. of DOS consoles.
+    Cp437,
+}
+
+// The characters of the bytes 0x80 to 0x9F, the others being as in Latin-1. The five unassigned
+// bytes are read as C1 controls, as by the WHATWG Encoding Standard.
+const WINDOWS_1252: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž', '\u{8F}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9D}', 'ž', 'Ÿ',
+];
+
+// The characters of the bytes 0x80 to 0xFF
+const MAC_ROMAN: [char; 128] = [
+    'Ä', 'Å', 'Ç', 'É', 'Ñ', 'Ö', 'Ü', 'á', 'à', 'â', 'ä', 'ã', 'å', 'ç', 'é', 'è', //
+    'ê', 'ë', 'í', 'ì', 'î', 'ï', 'ñ', 'ó', 'ò', 'ô', 'ö', 'õ', 'ú', 'ù', 'û', 'ü', //
+    '†', '°', '¢', '£', '§', '•', '¶', 'ß', '®', '©', '™', '´', '¨', '≠', 'Æ', 'Ø', //
+    '∞', '±', '≤', '≥', '¥', 'µ', '∂', '∑', '∏', 'π', '∫', 'ª', 'º', 'Ω', 'æ', 'ø', //
+    '¿', '¡', '¬', '√', 'ƒ', '≈', '∆', '«', '»', '…', '\u{A0}', 'À', 'Ã', 'Õ', 'Œ', 'œ', //
+    '–', '—', '“', '”', '‘', '’', '÷', '◊', 'ÿ', 'Ÿ', '⁄', '€', '‹', '›', 'ﬁ', 'ﬂ', //
+    '‡', '·', '‚', '„', '‰', 'Â', 'Ê', 'Á', 'Ë', 'È', 'Í', 'Î', 'Ï', 'Ì', 'Ó', 'Ô', //
+    '\u{F8FF}', 'Ò', 'Ú', 'Û', 'Ù', 'ı', 'ˆ', '˜', '¯', '˘', '˙', '˚', '¸', '˝', '˛', 'ˇ',
+];
+
+// The characters of the bytes 0x80 to 0xFF
+const CP437: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å', //
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ', //
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»', //
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐', //
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧', //
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀', //
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩', //
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{A0}',
+];
+
+impl Codec {
+    /// Tried in this order, so that the simplest codec is reported when several undo a layer
+    /// alike, e.g. Latin-1 rather than Windows-1252 for `Ã©`.
+    pub const ALL: [Codec; 4] = [
+        Codec::Latin1,
+        Codec::Windows1252,
+        Codec::MacRoman,
+        Codec::Cp437,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Codec::Latin1 => "ISO-8859-1",
+            Codec::Windows1252 => "windows-1252",
+            Codec::MacRoman => "macintosh",
+            Codec::Cp437 => "IBM437",
+        }
+    }
+
+    /// The character of `byte`, ASCII below 0x80.
+    pub fn decode_byte(self, byte: u8) -> char {
+        if byte.is_ascii() {
+            return char::from(byte);
+        }
+
+        let high = usize::from(byte - 0x80);
+        match self {
+            Codec::Latin1 => char::from(byte),
+            Codec::Windows1252 => WINDOWS_1252.get(high).copied().unwrap_or(char::from(byte)),
+            Codec::MacRoman => MAC_ROMAN[high],
+            Codec::Cp437 => CP437[high],
+        }
+    }
+
+    pub fn decode(self, bytes: &[u8]) -> String {
+        bytes.iter().map(|&b| self.decode_byte(b)).collect()
+    }
+
+    /// `None` if a character of `text` has no byte in the codec.
+    pub fn encode(self, text: &str) -> Option<Vec<u8>> {
+        text.chars()
+            .map(|c| match u8::try_from(c) {
+                Ok(byte) if byte.is_ascii() => Some(byte),
+                _ => (0x80..=0xFF).find(|&b| self.decode_byte(b) == c),
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Codec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Repair {
+    pub text: String,
+    /// The codecs the UTF-8 of the text was read with, in the order they were undone i.e. the
+    /// last misreading first.
+    pub layers: Vec<Codec>,
+}
+
+/// The text under one layer of mojibake in `codec`, if `text` can be one.
+fn peel(text: &str, codec: Codec) -> Option<String> {
+    let bytes = codec.encode(text)?;
+
+    // NOTE: ASCII reads the same in all codecs, so there is nothing to undo
+    if bytes.is_ascii() {
+        return None;
+    }
+
+    String::from_utf8(bytes).ok()
+}
+
+/// Undo the layers of mojibake of `text`, as many as make the text more likely.
+///
+/// Each layer is the one giving the most likely text. Correct text is returned as is, as its
+/// non-ASCII characters are seldom valid UTF-8 once encoded back.
+pub fn unmojibake(text: &str) -> Repair {
+    let mut repair = Repair {
+        text: text.to_string(),
+        layers: vec![],
+    };
+    let mut best = score(text);
+
+    loop {
+        let mut next = None;
+        for codec in Codec::ALL {
+            let Some(peeled) = peel(&repair.text, codec) else {
+                continue;
+            };
+
+            let likelihood = score(&peeled);
+            if likelihood > best {
+                best = likelihood;
+                next = Some((codec, peeled));
+            }
+        }
+
+        let Some((codec, peeled)) = next else {
+            return repair;
+        };
+        repair.text = peeled;
+        repair.layers.push(codec);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{unmojibake, Codec};
+
+    // `text` read as the UTF-8 of `layers`, the first one last
+    fn garble(text: &str, layers: &[Codec]) -> String {
+        layers.iter().rev().fold(text.to_string(), |text, codec| {
+            codec.decode(text.as_bytes())
+        })
+    }
+
+    #[test]
+    fn matches_reference_codecs() {
+        let bytes: Vec<u8> = (0..=0xFF).collect();
+        for (codec, encoding) in [
+            (Codec::Windows1252, encoding_rs::WINDOWS_1252),
+            (Codec::MacRoman, encoding_rs::MACINTOSH),
+        ] {
+            let (reference, _) = encoding.decode_without_bom_handling(&bytes);
+            assert_eq!(codec.decode(&bytes), reference);
+            assert_eq!(codec.encode(&reference), Some(bytes.clone()));
+        }
+
+        assert_eq!(Codec::Cp437.decode(b"\x82\xb3\xe1"), "é│ß");
+        assert_eq!(Codec::Latin1.encode("→"), None);
+    }
+
+    #[test]
+    fn undoes_layers() {
+        let repair = unmojibake("pugilarÃ\u{83}Â£o");
+        assert_eq!(repair.text, "pugilarão");
+        assert_eq!(repair.layers, [Codec::Latin1, Codec::Latin1]);
+
+        assert_eq!(unmojibake("naÃ¯ve â€” cafÃ©").layers, [Codec::Windows1252]);
+
+        for layers in [
+            vec![Codec::MacRoman],
+            vec![Codec::Cp437],
+            vec![Codec::Cp437, Codec::Windows1252],
+            vec![Codec::Latin1, Codec::MacRoman, Codec::Latin1],
+        ] {
+            let repair = unmojibake(&garble("Größenordnung", &layers));
+            assert_eq!(repair.text, "Größenordnung");
+            assert_eq!(repair.layers, layers);
+        }
+    }
+
+    #[test]
+    fn keeps_correct_text() {
+        for text in ["skälen", "Ωμέγα", "déjà vu", "plain ASCII"] {
+            let repair = unmojibake(text);
+            assert_eq!(repair.text, text);
+            assert!(repair.layers.is_empty());
+        }
+    }
+}
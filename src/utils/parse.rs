@@ -76,3 +76,17 @@ pub fn parse_unsigned<T: FromStr>(input: &str) -> Vec<T> {
 
     numbers
 }
+
+// Bytes written as pairs of hex digits, e.g. `efbbbf`. `None` for an odd number of digits
+pub fn parse_hex(input: &str) -> Option<Vec<u8>> {
+    let input = input.trim().as_bytes();
+    if !input.len().is_multiple_of(2) {
+        return None;
+    }
+
+    let digit = |b: u8| char::from(b).to_digit(16);
+    input
+        .chunks_exact(2)
+        .map(|pair| Some((digit(pair[0])? * 16 + digit(pair[1])?) as u8))
+        .collect()
+}
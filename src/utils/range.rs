@@ -1,27 +1,38 @@
+use std::iter::Sum;
+use std::ops::Sub;
+
 // Ranges are exclusive i.e. [start, end[
-#[derive(Debug, Clone, Copy)]
-pub struct Range {
-    pub start: i32,
-    pub end: i32,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Range<T = i32> {
+    pub start: T,
+    pub end: T,
 }
 
-impl Range {
-    pub fn new(start: i32, end: i32) -> Self {
+impl<T: Ord + Copy> Range<T> {
+    pub fn new(start: T, end: T) -> Self {
         Range { start, end }
     }
 
-    pub fn intersect(&self, other: &Range) -> Option<(i32, i32)> {
-        if other.end <= self.start || self.end <= other.start {
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains(&self, point: T) -> bool {
+        self.start <= point && point < self.end
+    }
+
+    pub fn intersect(&self, other: &Range<T>) -> Option<Range<T>> {
+        if !self.overlap(other) {
             return None;
         }
 
         let start = std::cmp::max(self.start, other.start);
         let end = std::cmp::min(self.end, other.end);
 
-        Some((start, end))
+        Some(Range { start, end })
     }
 
-    pub fn union(&self, other: &Range) -> Vec<Range> {
+    pub fn union(&self, other: &Range<T>) -> Vec<Range<T>> {
         if self.overlap(other) {
             return vec![Range {
                 start: std::cmp::min(self.start, other.start),
@@ -29,31 +40,34 @@ impl Range {
             }];
         }
 
-        vec![self.clone(), other.clone()]
+        vec![*self, *other]
     }
 
-    pub fn overlap(&self, other: &Range) -> bool {
-        if other.end <= self.start || self.end <= other.start {
-            return false;
-        }
-        return true;
+    pub fn overlap(&self, other: &Range<T>) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+}
+
+impl<T: Sub<Output = T> + Copy> Range<T> {
+    pub fn length(&self) -> T {
+        self.end - self.start
     }
 }
 
-pub fn union_vecs(a: &Vec<Range>, b: &Vec<Range>) -> Vec<Range> {
-    let mut ranges = a.clone();
-    ranges.extend(b.clone());
+pub fn union_vecs<T: Ord + Copy>(a: &[Range<T>], b: &[Range<T>]) -> Vec<Range<T>> {
+    let mut ranges = a.to_vec();
+    ranges.extend_from_slice(b);
 
-    return union_vec(&ranges);
+    union_vec(&ranges)
 }
 
-pub fn union_vec(ranges: &Vec<Range>) -> Vec<Range> {
+pub fn union_vec<T: Ord + Copy>(ranges: &[Range<T>]) -> Vec<Range<T>> {
     if ranges.is_empty() {
         return vec![];
     }
 
     // Sort ranges by start
-    let mut sorted_ranges = ranges.clone();
+    let mut sorted_ranges = ranges.to_vec();
     sorted_ranges.sort_by_key(|r| r.start);
 
     let mut result = vec![];
@@ -73,3 +87,263 @@ pub fn union_vec(ranges: &Vec<Range>) -> Vec<Range> {
 
     result
 }
+
+// The parts covered by both `a` and `b`, both merged as by `union_vec`
+pub fn intersect_vecs<T: Ord + Copy>(a: &[Range<T>], b: &[Range<T>]) -> Vec<Range<T>> {
+    let mut result = vec![];
+    let (mut i, mut j) = (0, 0);
+
+    while i < a.len() && j < b.len() {
+        if let Some(range) = a[i].intersect(&b[j]) {
+            result.push(range);
+        }
+
+        // Move on from the range ending first
+        if a[i].end < b[j].end {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    result
+}
+
+// The parts of `a` not covered by `b`, both merged as by `union_vec`
+pub fn subtract_vecs<T: Ord + Copy>(a: &[Range<T>], b: &[Range<T>]) -> Vec<Range<T>> {
+    let mut result = vec![];
+    let mut j = 0;
+
+    for range in a {
+        let mut start = range.start;
+
+        // Skip ranges of `b` ending before this one
+        while j < b.len() && b[j].end <= start {
+            j += 1;
+        }
+
+        let mut k = j;
+        while k < b.len() && b[k].start < range.end {
+            if b[k].start > start {
+                result.push(Range::new(start, b[k].start));
+            }
+            start = std::cmp::max(start, b[k].end);
+            k += 1;
+        }
+
+        if start < range.end {
+            result.push(Range::new(start, range.end));
+        }
+    }
+
+    result
+}
+
+pub fn total_length<T: Sub<Output = T> + Sum + Copy>(ranges: &[Range<T>]) -> T {
+    ranges.iter().map(Range::length).sum()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A set of points stored as sorted, disjoint and non-adjacent ranges, e.g. of Unix timestamps or
+/// of byte offsets.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { ranges: vec![] }
+    }
+}
+
+impl<T: Ord + Copy> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet::default()
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        // Ranges overlapping or touching `range` are merged into it
+        let from = self.ranges.partition_point(|r| r.end < range.start);
+        let to = self.ranges.partition_point(|r| r.start <= range.end);
+
+        let mut merged = range;
+        if from < to {
+            merged.start = std::cmp::min(merged.start, self.ranges[from].start);
+            merged.end = std::cmp::max(merged.end, self.ranges[to - 1].end);
+        }
+
+        self.ranges.splice(from..to, [merged]);
+    }
+
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let from = self.ranges.partition_point(|r| r.end <= range.start);
+        let to = self.ranges.partition_point(|r| r.start < range.end);
+
+        // What's left of the first and last overlapping ranges
+        let mut rest = vec![];
+        if from < to {
+            let (first, last) = (self.ranges[from], self.ranges[to - 1]);
+            if first.start < range.start {
+                rest.push(Range::new(first.start, range.start));
+            }
+            if range.end < last.end {
+                rest.push(Range::new(range.end, last.end));
+            }
+        }
+
+        self.ranges.splice(from..to, rest);
+    }
+
+    /// The range containing `point`, if any.
+    pub fn get(&self, point: T) -> Option<&Range<T>> {
+        let index = self.ranges.partition_point(|r| r.end <= point);
+        self.ranges.get(index).filter(|r| r.contains(point))
+    }
+
+    pub fn contains(&self, point: T) -> bool {
+        self.get(point).is_some()
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        IntervalSet {
+            ranges: union_vecs(&self.ranges, &other.ranges),
+        }
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        IntervalSet {
+            ranges: intersect_vecs(&self.ranges, &other.ranges),
+        }
+    }
+
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        IntervalSet {
+            ranges: subtract_vecs(&self.ranges, &other.ranges),
+        }
+    }
+
+    /// The points of `bounds` not in the set.
+    pub fn complement(&self, bounds: Range<T>) -> IntervalSet<T> {
+        let bounds = IntervalSet::from_iter([bounds]);
+        bounds.difference(self)
+    }
+}
+
+impl<T: Ord + Copy + Sub<Output = T> + Sum> IntervalSet<T> {
+    pub fn total_length(&self) -> T {
+        total_length(&self.ranges)
+    }
+}
+
+impl<T: Ord + Copy> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let ranges: Vec<Range<T>> = iter.into_iter().filter(|r| !r.is_empty()).collect();
+        IntervalSet {
+            ranges: union_vec(&ranges),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Range<T>;
+    type IntoIter = std::slice::Iter<'a, Range<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.iter()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{intersect_vecs, subtract_vecs, total_length, IntervalSet, Range};
+
+    fn set(ranges: &[(i64, i64)]) -> IntervalSet<i64> {
+        ranges.iter().map(|&(s, e)| Range::new(s, e)).collect()
+    }
+
+    fn pairs<T: Copy>(ranges: &[Range<T>]) -> Vec<(T, T)> {
+        ranges.iter().map(|r| (r.start, r.end)).collect()
+    }
+
+    #[test]
+    fn operates_on_range_lists() {
+        let a = [Range::new(0, 10), Range::new(20, 30)];
+        let b = [Range::new(5, 25), Range::new(28, 40)];
+
+        assert_eq!(
+            pairs(&intersect_vecs(&a, &b)),
+            [(5, 10), (20, 25), (28, 30)]
+        );
+        assert_eq!(pairs(&subtract_vecs(&a, &b)), [(0, 5), (25, 28)]);
+        assert_eq!(pairs(&subtract_vecs(&b, &a)), [(10, 20), (30, 40)]);
+        assert_eq!(total_length(&a), 20);
+        assert_eq!(Range::new(0, 10).intersect(&Range::new(10, 20)), None);
+    }
+
+    #[test]
+    fn inserts_and_removes() {
+        let mut set = set(&[(0, 10), (20, 30)]);
+        set.insert(Range::new(10, 12));
+        set.insert(Range::new(40, 40));
+        assert_eq!(pairs(set.ranges()), [(0, 12), (20, 30)]);
+
+        set.insert(Range::new(5, 25));
+        assert_eq!(pairs(set.ranges()), [(0, 30)]);
+
+        set.remove(Range::new(10, 15));
+        set.remove(Range::new(28, 50));
+        assert_eq!(pairs(set.ranges()), [(0, 10), (15, 28)]);
+
+        set.remove(Range::new(-5, 20));
+        assert_eq!(pairs(set.ranges()), [(20, 28)]);
+    }
+
+    #[test]
+    fn answers_point_queries() {
+        let set = set(&[(0, 10), (20, 30)]);
+        assert!(set.contains(0));
+        assert!(!set.contains(10));
+        assert!(!set.contains(-1));
+        assert_eq!(set.get(25), Some(&Range::new(20, 30)));
+        assert_eq!(set.get(15), None);
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25)]);
+
+        assert_eq!(pairs(a.union(&b).ranges()), [(0, 30)]);
+        assert_eq!(pairs(a.intersection(&b).ranges()), [(5, 10), (20, 25)]);
+        assert_eq!(pairs(a.difference(&b).ranges()), [(0, 5), (25, 30)]);
+        assert_eq!(
+            pairs(a.complement(Range::new(-5, 35)).ranges()),
+            [(-5, 0), (10, 20), (30, 35)]
+        );
+        assert_eq!(a.total_length(), 20);
+
+        // Unix timestamps past 2038.
+        let year_3000 = set(&[(32_503_680_000, 32_535_216_000)]);
+        assert_eq!(year_3000.total_length(), 365 * 86_400);
+    }
+}
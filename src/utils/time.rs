@@ -1,9 +1,598 @@
-use chrono::{DateTime, NaiveDateTime, TimeZone};
+use chrono::{
+    DateTime, Datelike, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone,
+};
 use chrono_tz::Tz;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
 
-pub fn parse_date_with_iana(date_str: &str, iana: &str, date_format: &str) -> DateTime<Tz> {
-    let naive = NaiveDateTime::parse_from_str(date_str, date_format).unwrap();
-    let timezone: Tz = iana.parse().unwrap();
+use crate::utils::locale::{parse_pattern, Locale, PatternItem};
+use crate::utils::tzif::TzifZone;
+use crate::PuzzleError;
 
-    timezone.from_local_datetime(&naive).unwrap()
+/// A time zone local times can be resolved in, either a [`TimeZone`] of chrono or a [`TzifZone`].
+pub trait Zone {
+    type Tz: TimeZone;
+
+    fn local_datetime(&self, local: &NaiveDateTime) -> LocalResult<DateTime<Self::Tz>>;
+
+    /// The date and time at `timestamp` seconds since the epoch in this zone.
+    fn at_timestamp(&self, timestamp: i64) -> Option<DateTime<Self::Tz>>;
+}
+
+impl<T: TimeZone> Zone for T {
+    type Tz = T;
+
+    fn local_datetime(&self, local: &NaiveDateTime) -> LocalResult<DateTime<T>> {
+        self.from_local_datetime(local)
+    }
+
+    fn at_timestamp(&self, timestamp: i64) -> Option<DateTime<T>> {
+        self.timestamp_opt(timestamp, 0).single()
+    }
+}
+
+impl Zone for TzifZone {
+    type Tz = FixedOffset;
+
+    fn local_datetime(&self, local: &NaiveDateTime) -> LocalResult<DateTime<FixedOffset>> {
+        self.from_local_datetime(local)
+    }
+
+    fn at_timestamp(&self, timestamp: i64) -> Option<DateTime<FixedOffset>> {
+        self.from_timestamp(timestamp)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// How a local time that occurs twice (a fold, when clocks are turned back) or not at all (a gap,
+/// when clocks are turned forward) is resolved. The names follow `Temporal` of JavaScript.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Disambiguation {
+    /// The earlier time of a fold, gaps fail. Like [`LocalResult::earliest`].
+    Earliest,
+    /// The later time of a fold, gaps fail. Like [`LocalResult::latest`].
+    Latest,
+    /// Folds and gaps fail.
+    #[default]
+    Reject,
+    /// The earlier time of a fold, a time in a gap moves to the end of the gap: `02:30` becomes `03:00`.
+    ShiftForward,
+    /// The earlier time of a fold, a time in a gap moves forward by the length of the gap: `02:30`
+    /// becomes `03:30`. What `Temporal` does by default.
+    Compatible,
+}
+
+/// A local time in a zone, which may be ambiguous or skipped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LocalTime<T: TimeZone> {
+    Single(DateTime<T>),
+    /// The local time occurs twice, once with the offset before the transition and once with the
+    /// offset after.
+    Fold {
+        earlier: DateTime<T>,
+        later: DateTime<T>,
+    },
+    /// The local time is skipped. `earlier` and `later` read it with the offset after and before
+    /// the transition, i.e. move it back and forward by the length of the gap, `transition` is
+    /// the end of the gap.
+    Gap {
+        earlier: DateTime<T>,
+        later: DateTime<T>,
+        transition: DateTime<T>,
+    },
+}
+
+impl<T: TimeZone> LocalTime<T> {
+    pub fn resolve(&self, disambiguation: Disambiguation) -> Option<DateTime<T>> {
+        use Disambiguation::*;
+
+        match (self, disambiguation) {
+            (LocalTime::Single(date), _) => Some(date.clone()),
+            (LocalTime::Fold { earlier, .. }, Earliest | ShiftForward | Compatible) => {
+                Some(earlier.clone())
+            }
+            (LocalTime::Fold { later, .. }, Latest) => Some(later.clone()),
+            (LocalTime::Gap { transition, .. }, ShiftForward) => Some(transition.clone()),
+            (LocalTime::Gap { later, .. }, Compatible) => Some(later.clone()),
+            _ => None,
+        }
+    }
+
+    /// The reading of the local time with the UTC offset `offset`, if it has one. Unlike
+    /// [`LocalTime::resolve`] this tells the two times of a fold apart.
+    pub fn with_offset(&self, offset: FixedOffset) -> Option<DateTime<T>> {
+        let candidates = match self {
+            LocalTime::Single(date) => vec![date],
+            LocalTime::Fold { earlier, later } => vec![earlier, later],
+            LocalTime::Gap { .. } => vec![],
+        };
+
+        candidates
+            .into_iter()
+            .find(|date| date.offset().fix() == offset)
+            .cloned()
+    }
+}
+
+/// Find out how `local` reads in `zone`. `None` if it is out of the range of dates.
+pub fn resolve_local<Z: Zone + ?Sized>(
+    zone: &Z,
+    local: &NaiveDateTime,
+) -> Option<LocalTime<Z::Tz>> {
+    match zone.local_datetime(local) {
+        LocalResult::Single(date) => return Some(LocalTime::Single(date)),
+        LocalResult::Ambiguous(earlier, later) => return Some(LocalTime::Fold { earlier, later }),
+        LocalResult::None => {}
+    }
+
+    // NOTE: UTC offsets are less than a day, so the transition of the gap lies within a day of
+    // the local time read as UTC.
+    let local_seconds = local.and_utc().timestamp();
+    let offset_at = |timestamp: i64| {
+        let date = zone.at_timestamp(timestamp)?;
+        Some(i64::from(date.offset().fix().local_minus_utc()))
+    };
+    let before = offset_at(local_seconds - 86_400)?;
+    let after = offset_at(local_seconds + 86_400)?;
+
+    // the first second with the offset after the gap.
+    let (mut lo, mut hi) = (local_seconds - after, local_seconds - before);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if offset_at(mid)? == after {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+
+    Some(LocalTime::Gap {
+        earlier: zone.at_timestamp(local_seconds - after)?,
+        later: zone.at_timestamp(local_seconds - before)?,
+        transition: zone.at_timestamp(lo)?,
+    })
+}
+
+/// Parse a local date in `zone`, resolving folds and gaps as `disambiguation` says.
+///
+/// The error has no location, use [`PuzzleError::locate`] to point it at the input.
+pub fn parse_local_date<Z: Zone + ?Sized>(
+    date_str: &str,
+    zone: &Z,
+    date_format: &str,
+    disambiguation: Disambiguation,
+) -> Result<DateTime<Z::Tz>, PuzzleError> {
+    let naive = NaiveDateTime::parse_from_str(date_str, date_format)
+        .map_err(|e| PuzzleError::new(format!("invalid date `{date_str}`: {e}")))?;
+
+    resolve_parsed(date_str, zone, &naive, disambiguation)
+}
+
+/// Resolve `naive`, parsed from `date_str`, in `zone`.
+fn resolve_parsed<Z: Zone + ?Sized>(
+    date_str: &str,
+    zone: &Z,
+    naive: &NaiveDateTime,
+    disambiguation: Disambiguation,
+) -> Result<DateTime<Z::Tz>, PuzzleError> {
+    let local = resolve_local(zone, naive)
+        .ok_or_else(|| PuzzleError::new(format!("date `{date_str}` is out of range")))?;
+
+    local.resolve(disambiguation).ok_or_else(|| match local {
+        LocalTime::Gap { .. } => PuzzleError::new(format!(
+            "local time `{date_str}` is skipped by a transition"
+        )),
+        _ => PuzzleError::new(format!("local time `{date_str}` is ambiguous")),
+    })
+}
+
+fn parse_iana(iana: &str) -> Result<Tz, PuzzleError> {
+    iana.parse()
+        .map_err(|_| PuzzleError::new(format!("unknown time zone `{iana}`")))
+}
+
+/// Parse a local date in the IANA time zone `iana`.
+///
+/// Fails if the date or time zone can't be parsed, or if the local time is ambiguous or skipped
+/// and `disambiguation` doesn't resolve it. The error has no location, use [`PuzzleError::locate`]
+/// to point it at the input.
+pub fn parse_date_with_iana(
+    date_str: &str,
+    iana: &str,
+    date_format: &str,
+    disambiguation: Disambiguation,
+) -> Result<DateTime<Tz>, PuzzleError> {
+    let timezone = parse_iana(iana)?;
+
+    parse_local_date(date_str, &timezone, date_format, disambiguation)
+        .map_err(|e| PuzzleError::new(format!("{} in {iana}", e.message)))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A layout of [`Locale`] compiled to a regex.
+struct Layout {
+    locale: Locale,
+    pattern: &'static str,
+    regex: Regex,
+}
+
+struct Layouts {
+    /// Match the start of a date.
+    dates: Vec<Layout>,
+    /// Match what follows a date, including the connector.
+    times: Vec<Layout>,
+}
+
+lazy_static! {
+    static ref LAYOUTS: Layouts = {
+        let mut dates = vec![];
+        let mut times = vec![];
+
+        for locale in Locale::ALL {
+            for &pattern in locale.date_layouts() {
+                let regex = format!(r"(?i)^\s*{}", layout_regex(locale, pattern));
+                let regex = Regex::new(&regex).unwrap();
+                dates.push(Layout {
+                    locale,
+                    pattern,
+                    regex,
+                });
+            }
+
+            let connectors = alternatives(locale.time_connectors());
+            for &pattern in locale.time_layouts() {
+                let regex = format!(
+                    r"(?i)^\s*(?:{connectors}\s*)?{}\s*$",
+                    layout_regex(locale, pattern)
+                );
+                let regex = Regex::new(&regex).unwrap();
+                times.push(Layout {
+                    locale,
+                    pattern,
+                    regex,
+                });
+            }
+        }
+
+        Layouts { dates, times }
+    };
+}
+
+/// A regex matching any of `names`, longest first, with an optional trailing period.
+fn alternatives(names: &[&str]) -> String {
+    let mut names: Vec<String> = names
+        .iter()
+        .map(|name| regex::escape(name.trim_end_matches('.')))
+        .collect();
+    names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+    names.dedup();
+
+    format!(r"(?:{})\.?", names.join("|"))
+}
+
+/// Translate a pattern of [`Locale::date_layouts`] or [`Locale::time_layouts`] into a regex.
+fn layout_regex(locale: Locale, pattern: &str) -> String {
+    let mut regex = String::new();
+
+    for item in parse_pattern(pattern) {
+        let (c, count) = match item {
+            PatternItem::Literal(literal) => {
+                for c in literal.chars() {
+                    match c {
+                        c if c.is_whitespace() => regex.push_str(r"\s*"),
+                        c => regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+                    }
+                }
+                continue;
+            }
+            PatternItem::Field(c, count) => (c, count),
+        };
+
+        let names = |full: &[&'static str], abbreviated: &[&'static str]| {
+            alternatives(&[full, abbreviated].concat())
+        };
+        let group = match (c, count) {
+            ('E', _) => format!(
+                "(?P<weekday>{})",
+                names(locale.weekdays(), locale.weekdays_abbreviated())
+            ),
+            ('M', 3..) => format!(
+                "(?P<month_name>{})",
+                names(locale.months(), locale.months_abbreviated())
+            ),
+            ('M', _) => r"(?P<month>\d{1,2})".to_string(),
+            ('d', _) if locale.ordinal_suffixes().is_empty() => r"(?P<day>\d{1,2})".to_string(),
+            ('d', _) => format!(
+                r"(?P<day>\d{{1,2}})(?:{})?",
+                locale.ordinal_suffixes().join("|")
+            ),
+            ('y', _) => r"(?P<year>\d{4})".to_string(),
+            ('H', _) => r"(?P<hour>\d{1,2})".to_string(),
+            ('h', _) => r"(?P<hour12>\d{1,2})".to_string(),
+            ('m', _) => r"(?P<minute>\d{2})".to_string(),
+            ('s', _) => r"(?P<second>\d{2})".to_string(),
+            ('a', _) => format!("(?P<period>{})", alternatives(&locale.day_periods())),
+            _ => panic!("unsupported pattern letter `{c}` in `{pattern}`"),
+        };
+        regex.push_str(&group);
+    }
+
+    regex
+}
+
+fn read_date(locale: Locale, caps: &Captures) -> Option<NaiveDate> {
+    let number = |name| caps.name(name)?.as_str().parse().ok();
+    let month = match caps.name("month_name") {
+        Some(name) => locale.parse_month(name.as_str())?,
+        None => number("month")?,
+    };
+    let date = NaiveDate::from_ymd_opt(number("year")? as i32, month, number("day")?)?;
+
+    // The weekday, if any, must be that of the date
+    match caps.name("weekday") {
+        Some(name) if locale.parse_weekday(name.as_str())? != date.weekday() => None,
+        _ => Some(date),
+    }
+}
+
+fn read_time(locale: Locale, caps: &Captures) -> Option<NaiveTime> {
+    let number = |name| caps.name(name).map_or(Some(0), |m| m.as_str().parse().ok());
+    let hour = match (caps.name("hour12"), caps.name("period")) {
+        (Some(_), Some(period)) => {
+            let hour = number("hour12")?;
+            if !(1..=12).contains(&hour) {
+                return None;
+            }
+            let period = period.as_str().trim_end_matches('.').to_lowercase();
+            let pm = period == locale.day_periods()[1].to_lowercase();
+            hour % 12 + if pm { 12 } else { 0 }
+        }
+        _ => number("hour")?,
+    };
+
+    NaiveTime::from_hms_opt(hour, number("minute")?, number("second")?)
+}
+
+/// Parse a date written in words or digits the way it is in any of `locales`, e.g.
+/// `Mar 04, 2020, 10:00`, `Dienstag, 5. März 2024 um 14:30` or `2024年3月5日(火) 14:30`.
+/// Without a time the date is at midnight.
+///
+/// The error lists the layouts tried and has no location, use [`PuzzleError::locate`] to point it
+/// at the input.
+pub fn parse_natural_local(
+    date_str: &str,
+    locales: &[Locale],
+) -> Result<NaiveDateTime, PuzzleError> {
+    let dates = LAYOUTS.dates.iter().filter(|l| locales.contains(&l.locale));
+    let times = |locale| LAYOUTS.times.iter().filter(move |l| l.locale == locale);
+
+    for layout in dates.clone() {
+        let Some(caps) = layout.regex.captures(date_str) else {
+            continue;
+        };
+        let Some(date) = read_date(layout.locale, &caps) else {
+            continue;
+        };
+
+        let rest = &date_str[caps.get(0).unwrap().end()..];
+        if rest.trim().is_empty() {
+            return Ok(date.and_time(NaiveTime::MIN));
+        }
+
+        let time = times(layout.locale).find_map(|time| {
+            let caps = time.regex.captures(rest)?;
+            read_time(time.locale, &caps)
+        });
+        if let Some(time) = time {
+            return Ok(date.and_time(time));
+        }
+    }
+
+    let tried = |layouts: &mut dyn Iterator<Item = &Layout>| {
+        layouts
+            .map(|l| format!("`{}` ({})", l.pattern, l.locale.code()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let tried_times = tried(&mut locales.iter().flat_map(|&locale| times(locale)));
+
+    Err(PuzzleError::new(format!(
+        "no layout matches date `{date_str}`, tried dates {} and times {tried_times}",
+        tried(&mut dates.into_iter())
+    )))
+}
+
+/// Parse a date as [`parse_natural_local`] does, in the IANA time zone `iana`.
+///
+/// Fails as [`parse_date_with_iana`] does if the local time is ambiguous or skipped and
+/// `disambiguation` doesn't resolve it.
+pub fn parse_natural_date(
+    date_str: &str,
+    iana: &str,
+    locales: &[Locale],
+    disambiguation: Disambiguation,
+) -> Result<DateTime<Tz>, PuzzleError> {
+    let timezone = parse_iana(iana)?;
+    let naive = parse_natural_local(date_str, locales)?;
+
+    resolve_parsed(date_str, &timezone, &naive, disambiguation)
+        .map_err(|e| PuzzleError::new(format!("{} in {iana}", e.message)))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use chrono::{NaiveDateTime, Offset};
+    use chrono_tz::Tz;
+
+    use super::{
+        parse_date_with_iana, parse_natural_date, parse_natural_local, resolve_local,
+        Disambiguation, LocalTime,
+    };
+    use crate::utils::locale::Locale;
+
+    const FORMAT: &str = "%Y-%m-%d %H:%M";
+
+    fn local(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, FORMAT).unwrap()
+    }
+
+    fn resolve(tz: Tz, s: &str, disambiguation: Disambiguation) -> Option<String> {
+        let date = resolve_local(&tz, &local(s))?.resolve(disambiguation)?;
+        Some(date.format("%H:%M%:z").to_string())
+    }
+
+    #[test]
+    fn resolves_gaps() {
+        let berlin = Tz::Europe__Berlin;
+        assert!(matches!(
+            resolve_local(&berlin, &local("2024-03-31 02:30")),
+            Some(LocalTime::Gap { .. })
+        ));
+
+        assert_eq!(
+            resolve(berlin, "2024-03-31 02:30", Disambiguation::Earliest),
+            None
+        );
+        assert_eq!(
+            resolve(berlin, "2024-03-31 02:30", Disambiguation::Latest),
+            None
+        );
+        assert_eq!(
+            resolve(berlin, "2024-03-31 02:30", Disambiguation::Reject),
+            None
+        );
+        assert_eq!(
+            resolve(berlin, "2024-03-31 02:30", Disambiguation::ShiftForward).as_deref(),
+            Some("03:00+02:00")
+        );
+        assert_eq!(
+            resolve(berlin, "2024-03-31 02:30", Disambiguation::Compatible).as_deref(),
+            Some("03:30+02:00")
+        );
+
+        // Samoa skipped all of 2011-12-30.
+        assert_eq!(
+            resolve(
+                Tz::Pacific__Apia,
+                "2011-12-30 12:00",
+                Disambiguation::ShiftForward
+            )
+            .as_deref(),
+            Some("00:00+14:00")
+        );
+    }
+
+    #[test]
+    fn resolves_folds() {
+        let berlin = Tz::Europe__Berlin;
+        let fold = resolve_local(&berlin, &local("2024-10-27 02:30")).unwrap();
+        assert!(matches!(fold, LocalTime::Fold { .. }));
+
+        assert_eq!(
+            resolve(berlin, "2024-10-27 02:30", Disambiguation::Earliest).as_deref(),
+            Some("02:30+02:00")
+        );
+        assert_eq!(
+            resolve(berlin, "2024-10-27 02:30", Disambiguation::Latest).as_deref(),
+            Some("02:30+01:00")
+        );
+        assert_eq!(
+            resolve(berlin, "2024-10-27 02:30", Disambiguation::Reject),
+            None
+        );
+        assert_eq!(
+            resolve(berlin, "2024-10-27 02:30", Disambiguation::Compatible).as_deref(),
+            Some("02:30+02:00")
+        );
+
+        let cet = chrono::FixedOffset::east_opt(3600).unwrap();
+        assert_eq!(fold.with_offset(cet).unwrap().offset().fix(), cet);
+    }
+
+    #[test]
+    fn reports_unresolved_times() {
+        let error = parse_date_with_iana(
+            "2024-03-31 02:30",
+            "Europe/Berlin",
+            FORMAT,
+            Disambiguation::Reject,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.message,
+            "local time `2024-03-31 02:30` is skipped by a transition in Europe/Berlin"
+        );
+
+        let error = parse_date_with_iana(
+            "2024-10-27 02:30",
+            "Europe/Berlin",
+            FORMAT,
+            Disambiguation::Reject,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.message,
+            "local time `2024-10-27 02:30` is ambiguous in Europe/Berlin"
+        );
+    }
+
+    #[test]
+    fn parses_natural_dates() {
+        let parse = |s: &str| {
+            let date = parse_natural_local(s, &Locale::ALL).ok()?;
+            Some(date.format(FORMAT).to_string())
+        };
+
+        for (date_str, expected) in [
+            ("Mar 04, 2020, 10:00", "2020-03-04 10:00"),
+            ("Tuesday, March 5th, 2024 at 2:30 PM", "2024-03-05 14:30"),
+            ("Dienstag, 5. März 2024 um 14:30 Uhr", "2024-03-05 14:30"),
+            ("mardi 5 mars 2024 à 14 h 30", "2024-03-05 14:30"),
+            ("1er janv. 2024", "2024-01-01 00:00"),
+            ("5 de marzo de 2024, 14:30", "2024-03-05 14:30"),
+            ("martedì 5 marzo 2024 alle 14:30", "2024-03-05 14:30"),
+            (
+                "terça-feira, 5 de março de 2024 às 14:30",
+                "2024-03-05 14:30",
+            ),
+            ("dinsdag 5 maart 2024 om 14:30", "2024-03-05 14:30"),
+            ("tisdag 5 mars 2024 kl. 14:30", "2024-03-05 14:30"),
+            ("tirsdag den 5. marts 2024", "2024-03-05 00:00"),
+            ("wtorek, 5 marca 2024 o 14:30", "2024-03-05 14:30"),
+            ("tiistai 5. maaliskuuta 2024 klo 14.30", "2024-03-05 14:30"),
+            ("5 Mart 2024 Salı 14:30", "2024-03-05 14:30"),
+            ("2024年3月5日(火) 午後2:30", "2024-03-05 14:30"),
+            ("2024年3月5日火曜日 14時30分", "2024-03-05 14:30"),
+        ] {
+            assert_eq!(parse(date_str).as_deref(), Some(expected), "{date_str}");
+        }
+
+        // 2024-03-05 is not a Monday.
+        assert_eq!(parse("Monday, March 5, 2024"), None);
+    }
+
+    #[test]
+    fn reports_unparsed_natural_dates() {
+        let error = parse_natural_local("5 mars 2024", &[Locale::English]).unwrap_err();
+        assert!(error
+            .message
+            .starts_with("no layout matches date `5 mars 2024`, tried dates `MMMM d, y` (en), "));
+
+        let error = parse_natural_date(
+            "31. März 2024 um 02:30",
+            "Europe/Berlin",
+            &[Locale::German],
+            Disambiguation::Reject,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.message,
+            "local time `31. März 2024 um 02:30` is skipped by a transition in Europe/Berlin"
+        );
+    }
 }
@@ -0,0 +1,1108 @@
+/// Compiler for tz database sources (the `africa`, `europe`, … files of a `tzdata` release),
+/// which turns their Rule, Zone and Link lines into TZif zones like `zic` does.
+///
+/// Transitions are written out up to [`LAST_YEAR`]. Zones whose current rules can be expressed
+/// as a POSIX TZ string get it as footer, which covers the years after that.
+///
+/// ```text
+/// # Rule NAME FROM TO   -  IN  ON      AT    SAVE LETTER/S
+/// Rule    EU   1981 max  -  Mar lastSun 1:00u 1:00 S
+/// Rule    EU   1996 max  -  Oct lastSun 1:00u 0    -
+/// # Zone NAME          STDOFF  RULES FORMAT [UNTIL]
+/// Zone    Europe/Berlin 0:53:28 -     LMT    1893 Apr
+///                       1:00    EU    CE%sT
+/// # Link TARGET        LINK-NAME
+/// Link    Europe/Berlin Arctic/Longyearbyen
+/// ```
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use hashbrown::HashMap;
+
+use crate::utils::tzif::{LocalTimeType, TzifError, TzifZone};
+
+/// The last year transitions are generated for.
+pub const LAST_YEAR: i32 = 2037;
+
+/// The source files compiled by default, as in the `Makefile` of the tz database.
+pub const SOURCE_FILES: [&str; 9] = [
+    "africa",
+    "antarctica",
+    "asia",
+    "australasia",
+    "europe",
+    "northamerica",
+    "southamerica",
+    "etcetera",
+    "backward",
+];
+
+#[derive(Debug)]
+pub enum TzdataError {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl Display for TzdataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TzdataError::Parser(e) => write!(f, "{e}"),
+            TzdataError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for TzdataError {
+    fn from(e: io::Error) -> Self {
+        TzdataError::IO(e)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// How the time of a transition is to be read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TimeKind {
+    /// Local wall clock time, the default.
+    Wall,
+    /// Local standard time (suffix `s`).
+    Standard,
+    /// Universal time (suffix `u`, `g` or `z`).
+    Universal,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DaySpec {
+    Day(u32),
+    Last(Weekday),
+    OnOrAfter(Weekday, u32),
+    OnOrBefore(Weekday, u32),
+}
+
+impl DaySpec {
+    fn date(self, year: i32, month: u32) -> Option<NaiveDate> {
+        match self {
+            DaySpec::Day(day) => NaiveDate::from_ymd_opt(year, month, day),
+            DaySpec::Last(weekday) => {
+                let next_month = match month {
+                    12 => NaiveDate::from_ymd_opt(year + 1, 1, 1)?,
+                    _ => NaiveDate::from_ymd_opt(year, month + 1, 1)?,
+                };
+                let last = next_month.pred_opt()?;
+                let back = (last.weekday().num_days_from_monday() + 7
+                    - weekday.num_days_from_monday())
+                    % 7;
+                Some(last - Duration::days(i64::from(back)))
+            }
+            // NOTE: may end up in the next or previous month, e.g. `Sun>=31`.
+            DaySpec::OnOrAfter(weekday, day) => {
+                let date = NaiveDate::from_ymd_opt(year, month, day)?;
+                let ahead = (weekday.num_days_from_monday() + 7
+                    - date.weekday().num_days_from_monday())
+                    % 7;
+                Some(date + Duration::days(i64::from(ahead)))
+            }
+            DaySpec::OnOrBefore(weekday, day) => {
+                let date = NaiveDate::from_ymd_opt(year, month, day)?;
+                let back = (date.weekday().num_days_from_monday() + 7
+                    - weekday.num_days_from_monday())
+                    % 7;
+                Some(date - Duration::days(i64::from(back)))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Rule {
+    from: i32,
+    to: i32,
+    month: u32,
+    on: DaySpec,
+    at: i32,
+    at_kind: TimeKind,
+    save: i32,
+    letters: String,
+}
+
+impl Rule {
+    /// Seconds since the epoch of the transition in `year`, in local time (not yet adjusted by an offset).
+    fn local_seconds(&self, year: i32) -> Option<i64> {
+        let date = self.on.date(year, self.month)?;
+        Some(date.and_hms_opt(0, 0, 0)?.and_utc().timestamp() + i64::from(self.at))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum PeriodRules {
+    None,
+    Fixed(i32),
+    Named(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Until {
+    /// Local seconds since the epoch.
+    local: i64,
+    year: i32,
+    kind: TimeKind,
+}
+
+impl Until {
+    fn utc(self, stdoff: i32, save: i32) -> i64 {
+        self.local
+            - i64::from(match self.kind {
+                TimeKind::Wall => stdoff + save,
+                TimeKind::Standard => stdoff,
+                TimeKind::Universal => 0,
+            })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ZonePeriod {
+    stdoff: i32,
+    rules: PeriodRules,
+    format: String,
+    until: Option<Until>,
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Rules, zones and links read from tz database sources.
+#[derive(Clone, Debug, Default)]
+pub struct Tzdata {
+    rules: HashMap<String, Vec<Rule>>,
+    zones: HashMap<String, Vec<ZonePeriod>>,
+    /// Link name to target.
+    links: HashMap<String, String>,
+}
+
+impl Tzdata {
+    /// Read the [`SOURCE_FILES`] that exist in `dir`.
+    pub fn read_dir(dir: &Path) -> Result<Self, TzdataError> {
+        let mut tzdata = Tzdata::default();
+        let mut found = false;
+
+        for file in SOURCE_FILES {
+            let path = dir.join(file);
+            if path.exists() {
+                tzdata.parse(&fs::read_to_string(path)?)?;
+                found = true;
+            }
+        }
+
+        if !found {
+            return Err(TzdataError::Parser(format!(
+                "no tzdata sources in {}, expecting e.g. `europe`.",
+                dir.display()
+            )));
+        }
+
+        Ok(tzdata)
+    }
+
+    /// Add the rules, zones and links of a source file.
+    pub fn parse(&mut self, source: &str) -> Result<(), TzdataError> {
+        let mut zone: Option<String> = None;
+
+        for (i, line) in source.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default();
+            let fields: Vec<&str> = line.split_whitespace().collect();
+
+            let Some(first) = fields.first() else {
+                continue;
+            };
+
+            let error = |e: String| TzdataError::Parser(format!("line {}: {e}", i + 1));
+
+            // NOTE: continuation lines of a zone start with whitespace.
+            let keyword = if line.starts_with(char::is_whitespace) {
+                ""
+            } else {
+                first
+            };
+
+            match keyword.chars().next().map(|c| c.to_ascii_lowercase()) {
+                Some('r') => {
+                    let (name, rule) = parse_rule(&fields).map_err(error)?;
+                    self.rules.entry(name).or_default().push(rule);
+                }
+                Some('z') => {
+                    let name = fields
+                        .get(1)
+                        .ok_or_else(|| error("zone without name".into()))?;
+                    let period = parse_period(&fields[2..]).map_err(error)?;
+                    let has_until = period.until.is_some();
+
+                    self.zones.insert(name.to_string(), vec![period]);
+                    zone = has_until.then(|| name.to_string());
+                }
+                Some('l') => {
+                    let [_, target, name] = fields[..] else {
+                        return Err(error("expected `Link TARGET LINK-NAME`".into()));
+                    };
+                    self.links.insert(name.to_string(), target.to_string());
+                }
+                None => {
+                    let name = zone
+                        .clone()
+                        .ok_or_else(|| error("continuation line without zone".into()))?;
+                    let period = parse_period(&fields).map_err(error)?;
+                    if period.until.is_none() {
+                        zone = None;
+                    }
+                    self.zones.get_mut(&name).unwrap().push(period);
+                }
+                Some(_) => return Err(error(format!("unknown line type `{first}`"))),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Names of all zones, excluding links.
+    pub fn zone_names(&self) -> impl Iterator<Item = &String> {
+        self.zones.keys()
+    }
+
+    /// Links as pairs of name and target.
+    pub fn links(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.links.iter()
+    }
+
+    /// Compile a zone or link to the contents of a TZif file.
+    pub fn compile(&self, name: &str) -> Result<Vec<u8>, TzdataError> {
+        let target = self.links.get(name).map_or(name, String::as_str);
+
+        let periods = self
+            .zones
+            .get(target)
+            .ok_or_else(|| TzdataError::Parser(format!("unknown zone `{name}`")))?;
+
+        Ok(ZoneCompiler::new(self).compile(periods)?.to_tzif())
+    }
+
+    /// Compile a zone or link into memory.
+    pub fn zone(&self, name: &str) -> Result<TzifZone, TzdataError> {
+        TzifZone::parse(&self.compile(name)?).map_err(|e| match e {
+            TzifError::Parser(e) => TzdataError::Parser(e),
+            TzifError::IO(e) => TzdataError::IO(e),
+        })
+    }
+
+    /// Compile every zone and link to a TZif file in `out`, e.g. `out/Europe/Berlin`.
+    /// Returns the number of zones and links written.
+    pub fn write_to(&self, out: &Path) -> Result<(usize, usize), TzdataError> {
+        let write = |name: &str| -> Result<(), TzdataError> {
+            let path = zone_path(out, name)?;
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, self.compile(name)?)?;
+            Ok(())
+        };
+
+        for name in self.zones.keys() {
+            write(name)?;
+        }
+
+        for name in self.links.keys() {
+            write(name)?;
+        }
+
+        Ok((self.zones.len(), self.links.len()))
+    }
+}
+
+/// Path of a zone below `out`, rejecting names that would escape it.
+fn zone_path(out: &Path, name: &str) -> Result<PathBuf, TzdataError> {
+    if name
+        .split('/')
+        .any(|part| part.is_empty() || part == "." || part == "..")
+    {
+        return Err(TzdataError::Parser(format!("invalid zone name `{name}`")));
+    }
+    Ok(out.join(name))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Whether `s` is `word`, abbreviated to at least `min_len` characters.
+fn is_keyword(s: &str, word: &str, min_len: usize) -> bool {
+    s.len() >= min_len && word.starts_with(&s.to_ascii_lowercase())
+}
+
+/// Match a possibly abbreviated word against `words` case-insensitively, e.g. `Apr` for `April`.
+fn match_word(s: &str, words: &[&str]) -> Option<usize> {
+    let s = s.to_ascii_lowercase();
+    words
+        .iter()
+        .position(|word| !s.is_empty() && word.to_ascii_lowercase().starts_with(&s))
+}
+
+static MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+static WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+fn parse_month(s: &str) -> Result<u32, String> {
+    match_word(s, &MONTHS)
+        .map(|i| i as u32 + 1)
+        .ok_or_else(|| format!("invalid month `{s}`"))
+}
+
+fn parse_weekday(s: &str) -> Result<Weekday, String> {
+    match_word(s, &WEEKDAYS)
+        .map(|i| Weekday::try_from(i as u8).unwrap())
+        .ok_or_else(|| format!("invalid weekday `{s}`"))
+}
+
+fn parse_number<T: std::str::FromStr>(s: &str) -> Result<T, String> {
+    s.parse().map_err(|_| format!("invalid number `{s}`"))
+}
+
+fn parse_day(s: &str) -> Result<DaySpec, String> {
+    if let Some(weekday) = s.strip_prefix("last") {
+        return Ok(DaySpec::Last(parse_weekday(weekday)?));
+    }
+    if let Some((weekday, day)) = s.split_once(">=") {
+        return Ok(DaySpec::OnOrAfter(
+            parse_weekday(weekday)?,
+            parse_number(day)?,
+        ));
+    }
+    if let Some((weekday, day)) = s.split_once("<=") {
+        return Ok(DaySpec::OnOrBefore(
+            parse_weekday(weekday)?,
+            parse_number(day)?,
+        ));
+    }
+    Ok(DaySpec::Day(parse_number(s)?))
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let next_month = match month {
+        12 => NaiveDate::from_ymd_opt(year + 1, 1, 1),
+        _ => NaiveDate::from_ymd_opt(year, month + 1, 1),
+    };
+    next_month
+        .and_then(|d| d.pred_opt())
+        .map_or(31, |d| d.day())
+}
+
+/// `[-]h[:mm[:ss]]` in seconds, `-` being zero. Fractional seconds are rounded.
+fn parse_duration(s: &str) -> Result<i32, String> {
+    if s == "-" {
+        return Ok(0);
+    }
+
+    let (sign, rest) = match s.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, s),
+    };
+
+    let mut seconds = 0.0;
+    for (i, part) in rest.split(':').enumerate() {
+        let value: f64 = parse_number(part)?;
+        seconds += value
+            * [3600.0, 60.0, 1.0]
+                .get(i)
+                .ok_or_else(|| format!("invalid time `{s}`"))?;
+    }
+
+    Ok(sign * seconds.round() as i32)
+}
+
+/// A time with an optional suffix of its kind, e.g. `2:00s`.
+fn parse_time(s: &str) -> Result<(i32, TimeKind), String> {
+    let (time, kind) = match s.char_indices().last() {
+        Some((i, 'w')) => (&s[..i], TimeKind::Wall),
+        Some((i, 's')) => (&s[..i], TimeKind::Standard),
+        Some((i, 'u' | 'g' | 'z')) => (&s[..i], TimeKind::Universal),
+        _ => (s, TimeKind::Wall),
+    };
+    Ok((parse_duration(time)?, kind))
+}
+
+/// `Rule NAME FROM TO - IN ON AT SAVE LETTER/S`
+fn parse_rule(fields: &[&str]) -> Result<(String, Rule), String> {
+    let [_, name, from, to, _, month, on, at, save, letters] = fields[..] else {
+        return Err("expected `Rule NAME FROM TO - IN ON AT SAVE LETTER/S`".into());
+    };
+
+    let from = if is_keyword(from, "minimum", 2) {
+        i32::MIN
+    } else {
+        parse_number(from)?
+    };
+
+    let to = if is_keyword(to, "maximum", 2) {
+        i32::MAX
+    } else if is_keyword(to, "only", 1) {
+        from
+    } else {
+        parse_number(to)?
+    };
+
+    let (at, at_kind) = parse_time(at)?;
+    // NOTE: a `s` or `d` suffix of SAVE only marks standard or daylight saving time explicitly.
+    let save = parse_duration(save.trim_end_matches(['s', 'd']))?;
+
+    Ok((
+        name.to_string(),
+        Rule {
+            from,
+            to,
+            month: parse_month(month)?,
+            on: parse_day(on)?,
+            at,
+            at_kind,
+            save,
+            letters: if letters == "-" {
+                String::new()
+            } else {
+                letters.to_string()
+            },
+        },
+    ))
+}
+
+/// `STDOFF RULES FORMAT [UNTIL]`
+fn parse_period(fields: &[&str]) -> Result<ZonePeriod, String> {
+    let [stdoff, rules, format, until @ ..] = fields else {
+        return Err("expected `STDOFF RULES FORMAT [UNTIL]`".into());
+    };
+
+    let rules = match *rules {
+        "-" => PeriodRules::None,
+        r if r.starts_with(|c: char| c.is_ascii_digit() || c == '-') => {
+            PeriodRules::Fixed(parse_duration(r.trim_end_matches(['s', 'd']))?)
+        }
+        r => PeriodRules::Named(r.to_string()),
+    };
+
+    let until = match until {
+        [] => None,
+        [year, rest @ ..] => {
+            let year: i32 = parse_number(year)?;
+            let month = rest.first().map_or(Ok(1), |m| parse_month(m))?;
+            let day = rest.get(1).map_or(Ok(DaySpec::Day(1)), |d| parse_day(d))?;
+            let (time, kind) = rest
+                .get(2)
+                .map_or(Ok((0, TimeKind::Wall)), |t| parse_time(t))?;
+
+            let date = day
+                .date(year, month)
+                .ok_or_else(|| format!("invalid date {year}-{month}"))?;
+
+            Some(Until {
+                local: date.and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp() + i64::from(time),
+                year,
+                kind,
+            })
+        }
+    };
+
+    Ok(ZonePeriod {
+        stdoff: parse_duration(stdoff)?,
+        rules,
+        format: format.to_string(),
+        until,
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An offset formatted for `%z`, e.g. `+05`, `-0330` or `+054508`.
+fn format_numeric_offset(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.abs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+
+    match (minutes, seconds) {
+        (0, 0) => format!("{sign}{hours:02}"),
+        (_, 0) => format!("{sign}{hours:02}{minutes:02}"),
+        _ => format!("{sign}{hours:02}{minutes:02}{seconds:02}"),
+    }
+}
+
+/// Expand a FORMAT such as `CE%sT`, `GMT/BST` or `%z`.
+fn format_abbreviation(format: &str, stdoff: i32, save: i32, letters: &str) -> String {
+    if let Some((std, dst)) = format.split_once('/') {
+        return if save == 0 { std } else { dst }.to_string();
+    }
+
+    format
+        .replace("%s", letters)
+        .replace("%z", &format_numeric_offset(stdoff + save))
+}
+
+/// A transition rule of a POSIX TZ string, e.g. `M3.5.0/3`, along with whether it needs version 3.
+fn posix_rule(rule: &Rule, time: i32) -> Option<(String, bool)> {
+    let weekday = |w: Weekday| w.num_days_from_sunday();
+
+    // NOTE: `Sat<=30` is `Sat>=24`, on the last day of the month it is `lastSat`.
+    let on = match rule.on {
+        DaySpec::OnOrBefore(w, day) if day == days_in_month(2020, rule.month) => DaySpec::Last(w),
+        DaySpec::OnOrBefore(w, day) if day > 7 => DaySpec::OnOrAfter(w, day - 6),
+        on => on,
+    };
+
+    let (day, shift) = match on {
+        DaySpec::Last(w) => (format!("M{}.5.{}", rule.month, weekday(w)), 0),
+        DaySpec::OnOrAfter(w, day) => {
+            // NOTE: `Fri>=23` is the Thursday on or after the 22nd, one day later.
+            let shift = (day - 1) % 7;
+            let week = (day - 1 - shift) / 7 + 1;
+            if week > 4 {
+                return None;
+            }
+            let w = (weekday(w) + 7 - shift) % 7;
+            (format!("M{}.{week}.{w}", rule.month), shift as i32)
+        }
+        DaySpec::OnOrBefore(..) => return None,
+        DaySpec::Day(day) => {
+            // NOTE: `Jn` never counts February 29.
+            let date = NaiveDate::from_ymd_opt(2021, rule.month, day)?;
+            (format!("J{}", date.ordinal()), 0)
+        }
+    };
+
+    let time = time + shift * 86_400;
+    let needs_v3 = !(0..=24 * 3600).contains(&time);
+
+    let time = match time {
+        7200 => String::new(),
+        time => format!("/{}", format_posix_offset(time)),
+    };
+
+    Some((format!("{day}{time}"), needs_v3))
+}
+
+/// `[-]h[:mm[:ss]]`
+fn format_posix_offset(seconds: i32) -> String {
+    let sign = if seconds < 0 { "-" } else { "" };
+    let seconds = seconds.abs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+
+    match (minutes, seconds) {
+        (0, 0) => format!("{sign}{hours}"),
+        (_, 0) => format!("{sign}{hours}:{minutes:02}"),
+        _ => format!("{sign}{hours}:{minutes:02}:{seconds:02}"),
+    }
+}
+
+fn posix_abbreviation(abbreviation: &str) -> String {
+    if abbreviation.len() >= 3 && abbreviation.chars().all(|c| c.is_ascii_alphabetic()) {
+        abbreviation.to_string()
+    } else {
+        format!("<{abbreviation}>")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A compiled zone, ready to be written as TZif.
+struct CompiledZone {
+    initial: LocalTimeType,
+    transitions: Vec<(i64, LocalTimeType)>,
+    footer: Option<String>,
+    needs_v3: bool,
+}
+
+struct ZoneCompiler<'a> {
+    tzdata: &'a Tzdata,
+    initial: Option<LocalTimeType>,
+    transitions: Vec<(i64, LocalTimeType)>,
+}
+
+impl<'a> ZoneCompiler<'a> {
+    fn new(tzdata: &'a Tzdata) -> Self {
+        Self {
+            tzdata,
+            initial: None,
+            transitions: vec![],
+        }
+    }
+
+    /// Record the local time type starting at `start`, the beginning of time if `None`.
+    fn emit(&mut self, start: Option<i64>, local_time_type: LocalTimeType) {
+        let Some(start) = start else {
+            self.initial = Some(local_time_type);
+            return;
+        };
+
+        while self.transitions.last().is_some_and(|(t, _)| *t >= start) {
+            self.transitions.pop();
+        }
+
+        let previous = self
+            .transitions
+            .last()
+            .map(|(_, ltt)| ltt)
+            .or(self.initial.as_ref());
+
+        if previous != Some(&local_time_type) {
+            self.transitions.push((start, local_time_type));
+        }
+    }
+
+    fn compile(mut self, periods: &[ZonePeriod]) -> Result<CompiledZone, TzdataError> {
+        let mut start: Option<i64> = None;
+        let mut previous = (0, 0);
+
+        for period in periods {
+            let save = match &period.rules {
+                PeriodRules::None => self.fixed(start, period, 0),
+                PeriodRules::Fixed(save) => self.fixed(start, period, *save),
+                PeriodRules::Named(name) => {
+                    let rules = self
+                        .tzdata
+                        .rules
+                        .get(name)
+                        .ok_or_else(|| TzdataError::Parser(format!("unknown rule `{name}`")))?;
+                    self.ruled(start, previous, period, rules)
+                }
+            };
+
+            start = period.until.map(|until| until.utc(period.stdoff, save));
+            previous = (period.stdoff, save);
+        }
+
+        let last = periods
+            .last()
+            .ok_or_else(|| TzdataError::Parser("zone without periods".into()))?;
+
+        let (footer, needs_v3) = match self.footer(last) {
+            Some((footer, needs_v3)) => (Some(footer), needs_v3),
+            None => (None, false),
+        };
+
+        Ok(CompiledZone {
+            initial: self.initial.unwrap(),
+            transitions: self.transitions,
+            footer,
+            needs_v3,
+        })
+    }
+
+    /// Compile a period without rules. Returns the save in effect at its end.
+    fn fixed(&mut self, start: Option<i64>, period: &ZonePeriod, save: i32) -> i32 {
+        let local_time_type = LocalTimeType {
+            utc_offset: period.stdoff + save,
+            is_dst: save != 0,
+            abbreviation: format_abbreviation(&period.format, period.stdoff, save, ""),
+        };
+        self.emit(start, local_time_type);
+        save
+    }
+
+    /// Compile a period following `rules`. Returns the save in effect at its end.
+    fn ruled(
+        &mut self,
+        start: Option<i64>,
+        (previous_stdoff, previous_save): (i32, i32),
+        period: &ZonePeriod,
+        rules: &[Rule],
+    ) -> i32 {
+        let stdoff = period.stdoff;
+
+        // NOTE: the save at the start of the period is the one of the last rule before it, which
+        // may lie years back.
+        let from_year = rules.iter().map(|r| r.from).min().unwrap_or(0).max(1800);
+        // NOTE: rules ending in a given year are written out in full, only `max` ones stop at LAST_YEAR.
+        let last_year = rules
+            .iter()
+            .map(|r| r.to)
+            .filter(|&to| to != i32::MAX)
+            .fold(LAST_YEAR, i32::max);
+        let to_year = period.until.map_or(last_year, |until| until.year);
+
+        let mut events: Vec<(i64, &Rule)> = vec![];
+        for year in from_year..=to_year {
+            for rule in rules.iter().filter(|r| r.from <= year && year <= r.to) {
+                if let Some(local) = rule.local_seconds(year) {
+                    events.push((local, rule));
+                }
+            }
+        }
+
+        // NOTE: sort by standard time, wall clock times of different rules are not comparable.
+        events.sort_by_key(|(local, rule)| match rule.at_kind {
+            TimeKind::Universal => *local + i64::from(stdoff),
+            _ => *local,
+        });
+
+        // Before any rule applies, standard time is in effect with the letters of the first rule without save.
+        let mut save = 0;
+        let mut letters = events
+            .iter()
+            .find(|(_, rule)| rule.save == 0)
+            .map(|(_, rule)| rule.letters.clone())
+            .unwrap_or_default();
+
+        let local_time_type = |save: i32, letters: &str| LocalTimeType {
+            utc_offset: stdoff + save,
+            is_dst: save != 0,
+            abbreviation: format_abbreviation(&period.format, stdoff, save, letters),
+        };
+
+        let mut has_started = false;
+
+        let utc = |local: i64, rule: &Rule, stdoff: i32, save: i32| {
+            local
+                - i64::from(match rule.at_kind {
+                    TimeKind::Wall => stdoff + save,
+                    TimeKind::Standard => stdoff,
+                    TimeKind::Universal => 0,
+                })
+        };
+
+        for (local, rule) in events {
+            let time = utc(local, rule, stdoff, save);
+
+            if period
+                .until
+                .is_some_and(|until| time >= until.utc(stdoff, save))
+            {
+                break;
+            }
+
+            // NOTE: like `zic`, rules up to the start are timed with the offsets of the previous
+            // period, so one taking effect along with the zone change is in effect from the start,
+            // e.g. `3:00 Russia MSK/MSD 1989 Mar 26 2:00s` followed by `2:00 Russia`.
+            if start.is_some_and(|start| utc(local, rule, previous_stdoff, previous_save) <= start)
+            {
+                save = rule.save;
+                letters.clone_from(&rule.letters);
+                continue;
+            }
+
+            if !has_started {
+                has_started = true;
+                if start != Some(time) {
+                    self.emit(start, local_time_type(save, &letters));
+                }
+            }
+
+            save = rule.save;
+            letters.clone_from(&rule.letters);
+            self.emit(Some(time), local_time_type(save, &letters));
+        }
+
+        if !has_started {
+            self.emit(start, local_time_type(save, &letters));
+        }
+
+        save
+    }
+
+    /// The POSIX TZ string describing the last period, if it can be expressed as one.
+    fn footer(&self, period: &ZonePeriod) -> Option<(String, bool)> {
+        // Without ongoing rules, the local time type of the last transition stays in effect.
+        let last = self
+            .transitions
+            .last()
+            .map(|(_, ltt)| ltt)
+            .or(self.initial.as_ref())?;
+        let fixed = format!(
+            "{}{}",
+            posix_abbreviation(&last.abbreviation),
+            format_posix_offset(-last.utc_offset)
+        );
+
+        let PeriodRules::Named(name) = &period.rules else {
+            return Some((fixed, false));
+        };
+
+        let ongoing: Vec<&Rule> = self.tzdata.rules[name]
+            .iter()
+            .filter(|r| r.to == i32::MAX)
+            .collect();
+
+        match ongoing[..] {
+            [] => Some((fixed, false)),
+            [a, b] => {
+                let (dst, std) = if a.save != 0 { (a, b) } else { (b, a) };
+                if std.save != 0 || dst.save == 0 {
+                    return None;
+                }
+
+                // POSIX rules are in local wall clock time of the time they end.
+                let local_time = |rule: &Rule, save_before: i32| match rule.at_kind {
+                    TimeKind::Wall => rule.at,
+                    TimeKind::Standard => rule.at + save_before,
+                    TimeKind::Universal => rule.at + period.stdoff + save_before,
+                };
+
+                let (start, start_v3) = posix_rule(dst, local_time(dst, 0))?;
+                let (end, end_v3) = posix_rule(std, local_time(std, dst.save))?;
+
+                let std_abbreviation =
+                    format_abbreviation(&period.format, period.stdoff, 0, &std.letters);
+                let dst_abbreviation =
+                    format_abbreviation(&period.format, period.stdoff, dst.save, &dst.letters);
+
+                let dst_offset = match dst.save {
+                    3600 => String::new(),
+                    save => format_posix_offset(-(period.stdoff + save)),
+                };
+
+                Some((
+                    format!(
+                        "{}{}{}{dst_offset},{start},{end}",
+                        posix_abbreviation(&std_abbreviation),
+                        format_posix_offset(-period.stdoff),
+                        posix_abbreviation(&dst_abbreviation),
+                    ),
+                    start_v3 || end_v3,
+                ))
+            }
+            _ => None,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl CompiledZone {
+    fn to_tzif(&self) -> Vec<u8> {
+        let mut types = vec![self.initial.clone()];
+        let mut indices = vec![];
+
+        for (_, local_time_type) in &self.transitions {
+            let index = types
+                .iter()
+                .position(|t| t == local_time_type)
+                .unwrap_or_else(|| {
+                    types.push(local_time_type.clone());
+                    types.len() - 1
+                });
+            indices.push(index as u8);
+        }
+
+        let mut abbreviations: Vec<u8> = vec![];
+        let mut abbreviation_indices = vec![];
+        for local_time_type in &types {
+            let needle = format!("{}\0", local_time_type.abbreviation);
+            let index = abbreviations
+                .windows(needle.len())
+                .position(|w| w == needle.as_bytes())
+                .unwrap_or_else(|| {
+                    abbreviations.extend(needle.as_bytes());
+                    abbreviations.len() - needle.len()
+                });
+            abbreviation_indices.push(index as u8);
+        }
+
+        let version = if self.needs_v3 { b'3' } else { b'2' };
+
+        let block = |bytes: &mut Vec<u8>, time_size: usize| {
+            let transitions: Vec<(i64, u8)> = self
+                .transitions
+                .iter()
+                .zip(&indices)
+                .map(|((time, _), &index)| (*time, index))
+                .filter(|(time, _)| time_size == 8 || i32::try_from(*time).is_ok())
+                .collect();
+
+            bytes.extend(b"TZif");
+            bytes.push(version);
+            bytes.extend([0; 15]);
+            for count in [0, 0, 0, transitions.len(), types.len(), abbreviations.len()] {
+                bytes.extend((count as u32).to_be_bytes());
+            }
+
+            for (time, _) in &transitions {
+                match time_size {
+                    4 => bytes.extend((*time as i32).to_be_bytes()),
+                    _ => bytes.extend(time.to_be_bytes()),
+                }
+            }
+            bytes.extend(transitions.iter().map(|(_, index)| index));
+
+            for (local_time_type, index) in types.iter().zip(&abbreviation_indices) {
+                bytes.extend(local_time_type.utc_offset.to_be_bytes());
+                bytes.push(u8::from(local_time_type.is_dst));
+                bytes.push(*index);
+            }
+            bytes.extend(&abbreviations);
+        };
+
+        let mut bytes = vec![];
+        block(&mut bytes, 4);
+        block(&mut bytes, 8);
+        bytes.extend(format!("\n{}\n", self.footer.as_deref().unwrap_or_default()).as_bytes());
+        bytes
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use chrono::{LocalResult, NaiveDateTime, TimeZone};
+    use chrono_tz::Tz;
+
+    use super::{Tzdata, TzdataError};
+
+    const SOURCE: &str = "
+# Rule  NAME  FROM  TO    -  IN   ON       AT     SAVE  LETTER/S
+Rule    EU    1977  1980  -  Apr  Sun>=1   1:00u  1:00  S
+Rule    EU    1977  only  -  Sep  lastSun  1:00u  0     -
+Rule    EU    1978  only  -  Oct   1       1:00u  0     -
+Rule    EU    1979  1995  -  Sep  lastSun  1:00u  0     -
+Rule    EU    1981  max   -  Mar  lastSun  1:00u  1:00  S
+Rule    EU    1996  max   -  Oct  lastSun  1:00u  0     -
+Rule    Zion  2013  max   -  Mar  Fri>=23  2:00   1:00  D
+Rule    Zion  2013  max   -  Oct  lastSun  2:00   0     S
+
+Zone    Europe/Berlin  0:53:28  -   LMT     1893 Apr
+                       1:00     -   CET     1980
+                       1:00     EU  CE%sT
+Zone    Asia/Jerusalem 2:20:54  -   LMT     1880
+                       2:00     Zion I%sT
+Zone    Asia/Kolkata   5:53:28  -   LMT     1854 Jun 28
+                       5:30     -   IST
+Zone    Etc/Test       0        -   %z      2000 Jan 1 0:00u
+                       -3:30    1:00 %z
+Link    Europe/Berlin  Arctic/Longyearbyen
+";
+
+    fn tzdata() -> Tzdata {
+        let mut tzdata = Tzdata::default();
+        tzdata.parse(SOURCE).unwrap();
+        tzdata
+    }
+
+    fn local(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn compiles_zones_with_rules() {
+        let zone = tzdata().zone("Europe/Berlin").unwrap();
+        let tz: Tz = "Europe/Berlin".parse().unwrap();
+
+        for timestamp in (400_000_000..2_500_000_000).step_by(86_400 * 3 + 1_799) {
+            let expected = tz.timestamp_opt(timestamp, 0).unwrap();
+            let actual = zone.from_timestamp(timestamp).unwrap();
+            assert_eq!(
+                actual.naive_local(),
+                expected.naive_local(),
+                "at {timestamp}"
+            );
+        }
+
+        assert_eq!(zone.local_time_type(-3_000_000_000).abbreviation, "LMT");
+        assert_eq!(zone.local_time_type(0).abbreviation, "CET");
+        assert_eq!(zone.local_time_type(1_720_000_000).abbreviation, "CEST");
+    }
+
+    #[test]
+    fn writes_posix_footers() {
+        let zone = tzdata().zone("Asia/Jerusalem").unwrap();
+        assert_eq!(zone.version, 3);
+
+        // after the last generated transition, only the footer applies.
+        let date = zone
+            .from_local_datetime(&local("2040-03-23 12:00"))
+            .unwrap();
+        assert_eq!(date.offset().local_minus_utc(), 3 * 3600);
+        assert_eq!(
+            zone.from_local_datetime(&local("2040-03-23 02:30")),
+            LocalResult::None
+        );
+    }
+
+    #[test]
+    fn formats_numeric_abbreviations() {
+        let zone = tzdata().zone("Etc/Test").unwrap();
+        assert_eq!(zone.local_time_type(0).abbreviation, "+00");
+        assert_eq!(zone.local_time_type(1_000_000_000).abbreviation, "-0230");
+        assert_eq!(zone.utc_offset(5_000_000_000), -9000);
+    }
+
+    #[test]
+    fn resolves_links() {
+        let tzdata = tzdata();
+        assert_eq!(
+            tzdata.compile("Arctic/Longyearbyen").unwrap(),
+            tzdata.compile("Europe/Berlin").unwrap()
+        );
+        assert_eq!(tzdata.zone("Asia/Kolkata").unwrap().utc_offset(0), 19800);
+    }
+
+    #[test]
+    fn follows_zic_edge_cases() {
+        let mut tzdata = Tzdata::default();
+        tzdata
+            .parse(
+                "
+Rule    Russia  1984  1995  -  Sep  lastSun  2:00s  0     -
+Rule    Russia  1985  2010  -  Mar  lastSun  2:00s  1:00  S
+Rule    Eire    1981  max   -  Mar  lastSun  1:00u  0     -
+Rule    Eire    1996  max   -  Oct  lastSun  1:00u  -1:00 -
+Rule    Pal     2059  max   -  Mar  Sat<=30  2:00   1:00  S
+Rule    Pal     2072  max   -  Oct  Sat<=30  2:00   0     -
+Zone    Europe/Vilnius  3:00  Russia  MSK/MSD  1989 Mar 26 2:00s
+                        2:00  Russia  EE%sT
+Zone    Europe/Dublin   1:00  Eire    IST/GMT
+Zone    Asia/Gaza       2:00  Pal     EE%sT
+",
+            )
+            .unwrap();
+
+        // the DST rule taking effect along with the zone change applies from its start.
+        let zone = tzdata.zone("Europe/Vilnius").unwrap();
+        assert_eq!(zone.local_time_type(606_871_925).abbreviation, "EEST");
+        assert_eq!(zone.utc_offset(606_871_925), 3 * 3600);
+
+        let footer = |name: &str| {
+            let bytes = tzdata.compile(name).unwrap();
+            let bytes = &bytes[..bytes.len() - 1];
+            let start = bytes.iter().rposition(|&b| b == b'\n').unwrap() + 1;
+            String::from_utf8(bytes[start..].to_vec()).unwrap()
+        };
+        assert_eq!(footer("Europe/Dublin"), "IST-1GMT0,M10.5.0,M3.5.0/1");
+        assert_eq!(footer("Asia/Gaza"), "EET-2EEST,M3.4.4/50,M10.4.4/50");
+    }
+
+    #[test]
+    fn rejects_malformed_sources() {
+        let mut tzdata = Tzdata::default();
+        assert!(matches!(
+            tzdata.parse("Rule EU 1977"),
+            Err(TzdataError::Parser(_))
+        ));
+        assert!(tzdata.parse("  1:00 EU CE%sT").is_err());
+        assert!(tzdata.parse("Zone Foo/Bar 1:00 - CET 1980 Foo").is_err());
+        assert!(tzdata.parse("Link Foo/Bar ../escape").is_ok());
+        assert!(tzdata.write_to(&std::env::temp_dir()).is_err());
+    }
+}
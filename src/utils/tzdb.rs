@@ -0,0 +1,259 @@
+/// Registry of the tz database versions installed side by side, e.g. by `cargo tzdb build`:
+/// `data/timezones/zoneinfo-2018c/Europe/Berlin`.
+///
+/// Zones are read when first requested and kept for later lookups. The registry can compare
+/// how the installed versions resolve a local time, which shows where tzdb releases changed.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use chrono::{LocalResult, NaiveDateTime};
+use hashbrown::HashMap;
+
+use crate::utils::tzif::{TzifError, TzifZone};
+
+/// Directory the versions are installed to by `cargo tzdb build`.
+pub const DEFAULT_ROOT: &str = "data/timezones";
+
+static VERSION_PREFIX: &str = "zoneinfo-";
+
+pub struct TzdbRegistry {
+    root: PathBuf,
+    versions: Vec<String>,
+    zones: Mutex<HashMap<(String, String), Arc<TzifZone>>>,
+}
+
+impl TzdbRegistry {
+    /// Find every `zoneinfo-<version>` directory in `root`.
+    pub fn discover(root: impl Into<PathBuf>) -> io::Result<Self> {
+        let root = root.into();
+
+        let mut versions = vec![];
+        for entry in fs::read_dir(&root)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            if let Some(version) = name.strip_prefix(VERSION_PREFIX)
+                && entry.path().is_dir()
+            {
+                versions.push(version.to_string());
+            }
+        }
+
+        // NOTE: tzdb versions like `2018c` order lexicographically.
+        versions.sort_unstable();
+
+        Ok(Self {
+            root,
+            versions,
+            zones: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// The installed versions, oldest first.
+    pub fn versions(&self) -> &[String] {
+        &self.versions
+    }
+
+    /// Directory holding the compiled zones of a version.
+    pub fn zoneinfo_dir(&self, version: &str) -> PathBuf {
+        let dir = self.root.join(format!("{VERSION_PREFIX}{version}"));
+        let installed = dir.join("usr/share/zoneinfo");
+
+        // NOTE: `make install` nests the zones, plain copies of a zoneinfo directory don't.
+        if installed.is_dir() {
+            installed
+        } else {
+            dir
+        }
+    }
+
+    /// A zone of a version, e.g. `zone("2023d", "Africa/Casablanca")`.
+    pub fn zone(&self, version: &str, name: &str) -> Result<Arc<TzifZone>, TzifError> {
+        let key = (version.to_string(), name.to_string());
+
+        if let Some(zone) = self.zones.lock().unwrap().get(&key) {
+            return Ok(zone.clone());
+        }
+
+        let zone = Arc::new(TzifZone::read(self.zoneinfo_dir(version).join(name))?);
+        self.zones.lock().unwrap().insert(key, zone.clone());
+        Ok(zone)
+    }
+
+    /// Resolve a local time of a zone in every installed version.
+    pub fn compare(&self, name: &str, local: &NaiveDateTime) -> OffsetComparison {
+        let mut comparison = OffsetComparison {
+            zone: name.to_string(),
+            local: *local,
+            groups: vec![],
+            missing: vec![],
+        };
+
+        for version in &self.versions {
+            let Ok(zone) = self.zone(version, name) else {
+                comparison.missing.push(version.clone());
+                continue;
+            };
+
+            let offsets = match zone.from_local_datetime(local) {
+                LocalResult::Single(date) => vec![date.offset().local_minus_utc()],
+                LocalResult::Ambiguous(earlier, later) => vec![
+                    earlier.offset().local_minus_utc(),
+                    later.offset().local_minus_utc(),
+                ],
+                LocalResult::None => vec![],
+            };
+
+            match comparison.groups.iter_mut().find(|(o, _)| *o == offsets) {
+                Some((_, versions)) => versions.push(version.clone()),
+                None => comparison.groups.push((offsets, vec![version.clone()])),
+            }
+        }
+
+        comparison
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// How the installed versions resolve a local time of a zone.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OffsetComparison {
+    pub zone: String,
+    pub local: NaiveDateTime,
+    /// The possible UTC offsets in seconds along with the versions resolving to them.
+    /// No offset means the local time is skipped, two mean it is ambiguous.
+    pub groups: Vec<(Vec<i32>, Vec<String>)>,
+    /// Versions the zone does not exist in.
+    pub missing: Vec<String>,
+}
+
+impl OffsetComparison {
+    /// Whether the versions resolve the local time differently.
+    pub fn disagrees(&self) -> bool {
+        self.groups.len() > 1
+    }
+}
+
+fn format_offset(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.abs();
+    format!("{sign}{:02}:{:02}", seconds / 3600, seconds % 3600 / 60)
+}
+
+impl Display for OffsetComparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at {}:", self.zone, self.local)?;
+
+        for (offsets, versions) in &self.groups {
+            let offsets = match offsets.as_slice() {
+                [] => "skipped".to_string(),
+                offsets => offsets
+                    .iter()
+                    .map(|&o| format_offset(o))
+                    .collect::<Vec<_>>()
+                    .join(" or "),
+            };
+            write!(f, "\n  {offsets}: {}", versions.join(", "))?;
+        }
+
+        if !self.missing.is_empty() {
+            write!(f, "\n  missing: {}", self.missing.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Registry of the versions installed to [`DEFAULT_ROOT`], relative to the current directory.
+pub fn installed() -> io::Result<TzdbRegistry> {
+    TzdbRegistry::discover(Path::new(DEFAULT_ROOT))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{fs, path::Path};
+
+    use chrono::NaiveDateTime;
+
+    use super::TzdbRegistry;
+    use crate::template::test_utils::temp_dir;
+
+    /// A TZif file without transitions, described by its footer alone.
+    fn footer_only_zone(footer: &str) -> Vec<u8> {
+        let header = |bytes: &mut Vec<u8>| {
+            bytes.extend(b"TZif2");
+            bytes.extend([0; 15]);
+            for count in [0u32, 0, 0, 0, 1, 4] {
+                bytes.extend(count.to_be_bytes());
+            }
+            bytes.extend(0i32.to_be_bytes());
+            bytes.extend([0, 0]);
+            bytes.extend(b"UTC\0");
+        };
+
+        let mut bytes = vec![];
+        header(&mut bytes);
+        header(&mut bytes);
+        bytes.extend(format!("\n{footer}\n").as_bytes());
+        bytes
+    }
+
+    fn install(root: &Path, version: &str, name: &str, footer: &str) {
+        let path = root.join(format!("zoneinfo-{version}/usr/share/zoneinfo/{name}"));
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, footer_only_zone(footer)).unwrap();
+    }
+
+    fn local(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn reports_disagreeing_versions() {
+        let root = temp_dir("tzdb");
+        install(&root, "2018c", "Test/Zone", "CET-1CEST,M3.5.0,M10.5.0/3");
+        install(&root, "2021b", "Test/Zone", "CET-1CEST,M3.5.0,M10.5.0/3");
+        install(&root, "2023d", "Test/Zone", "CET-1");
+        install(&root, "2023d", "Test/Other", "UTC0");
+        fs::write(root.join("README"), "").unwrap();
+
+        let registry = TzdbRegistry::discover(&root).unwrap();
+        assert_eq!(registry.versions(), ["2018c", "2021b", "2023d"]);
+
+        let comparison = registry.compare("Test/Zone", &local("2024-07-01 12:00"));
+        assert!(comparison.disagrees());
+        assert_eq!(
+            comparison.to_string(),
+            "Test/Zone at 2024-07-01 12:00:00:\n  +02:00: 2018c, 2021b\n  +01:00: 2023d"
+        );
+
+        let comparison = registry.compare("Test/Zone", &local("2024-03-31 02:30"));
+        assert_eq!(
+            comparison.groups[0],
+            (vec![], vec!["2018c".into(), "2021b".into()])
+        );
+
+        let comparison = registry.compare("Test/Other", &local("2024-01-01 00:00"));
+        assert!(!comparison.disagrees());
+        assert_eq!(comparison.missing, ["2018c", "2021b"]);
+    }
+
+    #[test]
+    fn caches_zones() {
+        let root = temp_dir("tzdb-cache");
+        install(&root, "2023d", "Test/Zone", "CET-1");
+
+        let registry = TzdbRegistry::discover(&root).unwrap();
+        let zone = registry.zone("2023d", "Test/Zone").unwrap();
+
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(registry.zone("2023d", "Test/Zone").unwrap(), zone);
+    }
+}
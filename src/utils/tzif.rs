@@ -0,0 +1,819 @@
+/// Reader for compiled time zone files (TZif, RFC 8536) as installed by the tz database,
+/// e.g. `/usr/share/zoneinfo/Europe/Berlin`.
+///
+/// Versions 1 to 4 are supported. Files of version 2 and up contain a second, 64-bit data block,
+/// which is used instead of the 32-bit one, and a footer holding a POSIX TZ string that describes
+/// the transitions after the last one listed. Leap second records are skipped.
+use std::{fmt::Display, fs, io, path::Path};
+
+use chrono::{DateTime, Datelike, Duration, FixedOffset, LocalResult, NaiveDate, NaiveDateTime};
+
+#[derive(Debug)]
+pub enum TzifError {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl Display for TzifError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TzifError::Parser(e) => write!(f, "{e}"),
+            TzifError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for TzifError {
+    fn from(e: io::Error) -> Self {
+        TzifError::IO(e)
+    }
+}
+
+fn parser_error(message: impl Display) -> TzifError {
+    TzifError::Parser(message.to_string())
+}
+
+/// A local time type, i.e. an offset from UTC in effect for a period of time.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocalTimeType {
+    /// Seconds east of UTC.
+    pub utc_offset: i32,
+    pub is_dst: bool,
+    /// Designation such as `CET` or `+0330`.
+    pub abbreviation: String,
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A compiled time zone.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TzifZone {
+    pub version: u8,
+    /// Transition times in seconds since the epoch, ascending.
+    transitions: Vec<i64>,
+    /// Index into `types` of the local time type starting at each transition.
+    transition_types: Vec<usize>,
+    types: Vec<LocalTimeType>,
+    /// Rule for the times after the last transition.
+    footer: Option<PosixTz>,
+}
+
+impl TzifZone {
+    /// Read a TZif file.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, TzifError> {
+        Self::parse(&fs::read(path)?)
+    }
+
+    /// Parse the contents of a TZif file.
+    pub fn parse(bytes: &[u8]) -> Result<Self, TzifError> {
+        let mut reader = Reader { bytes, pos: 0 };
+
+        let header = Header::read(&mut reader)?;
+
+        if header.version == 0 {
+            let zone = Self::read_block(&mut reader, &header, 4)?;
+            return Ok(zone);
+        }
+
+        // skip the 32-bit block, the 64-bit block following it covers a wider range.
+        reader.take(header.block_len(4))?;
+
+        let header = Header::read(&mut reader)?;
+        let mut zone = Self::read_block(&mut reader, &header, 8)?;
+
+        if reader.take(1)? != b"\n" {
+            return Err(parser_error("expected a newline before the footer"));
+        }
+
+        let rest = &reader.bytes[reader.pos..];
+        let end = rest
+            .iter()
+            .position(|&b| b == b'\n')
+            .ok_or_else(|| parser_error("footer is not terminated by a newline"))?;
+
+        let footer = std::str::from_utf8(&rest[..end])
+            .map_err(|_| parser_error("footer is not valid UTF-8"))?;
+
+        if !footer.is_empty() {
+            zone.footer = Some(footer.parse()?);
+        }
+
+        Ok(zone)
+    }
+
+    /// Read a data block whose transition times and leap second occurrences are `time_size` bytes wide.
+    fn read_block(
+        reader: &mut Reader,
+        header: &Header,
+        time_size: usize,
+    ) -> Result<Self, TzifError> {
+        let mut transitions = Vec::with_capacity(header.timecnt);
+        for _ in 0..header.timecnt {
+            transitions.push(reader.read_time(time_size)?);
+        }
+
+        let transition_types: Vec<usize> = reader
+            .take(header.timecnt)?
+            .iter()
+            .map(|&i| usize::from(i))
+            .collect();
+
+        let mut records = Vec::with_capacity(header.typecnt);
+        for _ in 0..header.typecnt {
+            let utc_offset = reader.read_i32()?;
+            let is_dst = reader.take(1)?[0] != 0;
+            let abbreviation_index = usize::from(reader.take(1)?[0]);
+            records.push((utc_offset, is_dst, abbreviation_index));
+        }
+
+        let abbreviations = reader.take(header.charcnt)?;
+
+        let types = records
+            .into_iter()
+            .map(|(utc_offset, is_dst, index)| {
+                let rest = abbreviations
+                    .get(index..)
+                    .ok_or_else(|| parser_error("abbreviation index out of range"))?;
+                let end = rest.iter().position(|&b| b == 0).unwrap_or(rest.len());
+
+                Ok(LocalTimeType {
+                    utc_offset,
+                    is_dst,
+                    abbreviation: String::from_utf8_lossy(&rest[..end]).to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>, TzifError>>()?;
+
+        if types.is_empty() {
+            return Err(parser_error("zone has no local time types"));
+        }
+
+        if transition_types.iter().any(|&i| i >= types.len()) {
+            return Err(parser_error("transition type index out of range"));
+        }
+
+        if transitions.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(parser_error("transition times are not ascending"));
+        }
+
+        reader.take(header.leapcnt * (time_size + 4))?;
+        reader.take(header.isstdcnt)?;
+        reader.take(header.isutcnt)?;
+
+        Ok(TzifZone {
+            version: header.version,
+            transitions,
+            transition_types,
+            types,
+            footer: None,
+        })
+    }
+
+    /// The local time type in effect at `timestamp` seconds since the epoch.
+    pub fn local_time_type(&self, timestamp: i64) -> LocalTimeType {
+        let index = self.transitions.partition_point(|&t| t <= timestamp);
+
+        if index == self.transitions.len()
+            && let Some(footer) = &self.footer
+        {
+            return footer.local_time_type(timestamp);
+        }
+
+        match index {
+            // NOTE: times before the first transition use the first local time type.
+            0 => self.types[0].clone(),
+            _ => self.types[self.transition_types[index - 1]].clone(),
+        }
+    }
+
+    /// The offset from UTC in seconds at `timestamp` seconds since the epoch.
+    pub fn utc_offset(&self, timestamp: i64) -> i32 {
+        self.local_time_type(timestamp).utc_offset
+    }
+
+    /// The date and time at `timestamp` seconds since the epoch in this zone.
+    pub fn from_timestamp(&self, timestamp: i64) -> Option<DateTime<FixedOffset>> {
+        let offset = FixedOffset::east_opt(self.utc_offset(timestamp))?;
+        Some(DateTime::from_timestamp(timestamp, 0)?.with_timezone(&offset))
+    }
+
+    /// Resolve a local date and time in this zone.
+    ///
+    /// Returns [`LocalResult::None`] for local times skipped by a transition (e.g. when clocks
+    /// move forward) and [`LocalResult::Ambiguous`] with the earlier and later candidate for
+    /// local times that occur twice.
+    pub fn from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<DateTime<FixedOffset>> {
+        let local_seconds = local.and_utc().timestamp();
+
+        // NOTE: transitions more than a day apart can't both affect the same local time.
+        let mut offsets = [
+            self.utc_offset(local_seconds - 86_400),
+            self.utc_offset(local_seconds + 86_400),
+        ];
+        offsets.sort_unstable_by_key(|&offset| -offset);
+
+        let mut candidates = offsets
+            .iter()
+            .filter(|&&offset| self.utc_offset(local_seconds - i64::from(offset)) == offset)
+            .filter_map(|&offset| {
+                let offset = FixedOffset::east_opt(offset)?;
+                local.and_local_timezone(offset).single()
+            });
+
+        match (candidates.next(), candidates.next()) {
+            (Some(a), Some(b)) if a != b => LocalResult::Ambiguous(a, b),
+            (Some(a), _) => LocalResult::Single(a),
+            _ => LocalResult::None,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], TzifError> {
+        let bytes = self
+            .bytes
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| parser_error("unexpected end of file"))?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, TzifError> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn read_i32(&mut self) -> Result<i32, TzifError> {
+        Ok(i32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn read_time(&mut self, size: usize) -> Result<i64, TzifError> {
+        match size {
+            4 => Ok(i64::from(self.read_i32()?)),
+            _ => Ok(i64::from_be_bytes(self.take(8)?.try_into().unwrap())),
+        }
+    }
+}
+
+struct Header {
+    /// 0 for version 1, otherwise the version number.
+    version: u8,
+    isutcnt: usize,
+    isstdcnt: usize,
+    leapcnt: usize,
+    timecnt: usize,
+    typecnt: usize,
+    charcnt: usize,
+}
+
+impl Header {
+    fn read(reader: &mut Reader) -> Result<Self, TzifError> {
+        if reader.take(4)? != b"TZif" {
+            return Err(parser_error("not a TZif file"));
+        }
+
+        let version = match reader.take(1)?[0] {
+            0 => 0,
+            v @ b'2'..=b'9' => v - b'0',
+            v => return Err(parser_error(format!("unknown version `{v}`"))),
+        };
+
+        reader.take(15)?;
+
+        let mut counts = [0; 6];
+        for count in &mut counts {
+            *count = reader.read_u32()? as usize;
+        }
+
+        let [isutcnt, isstdcnt, leapcnt, timecnt, typecnt, charcnt] = counts;
+
+        Ok(Header {
+            version,
+            isutcnt,
+            isstdcnt,
+            leapcnt,
+            timecnt,
+            typecnt,
+            charcnt,
+        })
+    }
+
+    /// Length in bytes of the data block following this header.
+    fn block_len(&self, time_size: usize) -> usize {
+        self.timecnt * (time_size + 1)
+            + self.typecnt * 6
+            + self.charcnt
+            + self.leapcnt * (time_size + 4)
+            + self.isstdcnt
+            + self.isutcnt
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A POSIX TZ string such as `CET-1CEST,M3.5.0,M10.5.0/3`, including the extensions of TZif
+/// version 3 (transition times from -167 to 167 hours).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PosixTz {
+    pub std: LocalTimeType,
+    pub dst: Option<PosixDst>,
+}
+
+/// Daylight saving time of a [`PosixTz`] along with the rules of when it starts and ends.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PosixDst {
+    pub local_time_type: LocalTimeType,
+    /// Start of DST in local standard time.
+    pub start: TransitionRule,
+    /// End of DST in local daylight saving time.
+    pub end: TransitionRule,
+}
+
+/// The day of a transition and its local time in seconds after midnight.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransitionRule {
+    pub day: RuleDay,
+    pub time: i32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RuleDay {
+    /// `Jn`: day of the year from 1 to 365, February 29 is never counted.
+    Julian1(u16),
+    /// `n`: day of the year from 0 to 365, February 29 is counted in leap years.
+    Julian0(u16),
+    /// `Mm.w.d`: weekday `d` (0 is Sunday) of week `w` (5 is the last) of month `m`.
+    MonthWeekday { month: u32, week: u32, weekday: u32 },
+}
+
+impl RuleDay {
+    fn date(self, year: i32) -> Option<NaiveDate> {
+        match self {
+            RuleDay::Julian1(n) => {
+                let is_leap = NaiveDate::from_ymd_opt(year, 2, 29).is_some();
+                let n = if is_leap && n >= 60 { n } else { n - 1 };
+                NaiveDate::from_yo_opt(year, u32::from(n) + 1)
+            }
+            RuleDay::Julian0(n) => NaiveDate::from_yo_opt(year, u32::from(n) + 1),
+            RuleDay::MonthWeekday {
+                month,
+                week,
+                weekday,
+            } => {
+                let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+                let first_weekday = first.weekday().num_days_from_sunday();
+                let mut day = 1 + (weekday + 7 - first_weekday) % 7 + (week - 1) * 7;
+
+                while NaiveDate::from_ymd_opt(year, month, day).is_none() {
+                    day -= 7;
+                }
+
+                NaiveDate::from_ymd_opt(year, month, day)
+            }
+        }
+    }
+}
+
+impl TransitionRule {
+    /// The transition of `year` in seconds since the epoch, for a local time with `utc_offset`.
+    fn timestamp(self, year: i32, utc_offset: i32) -> Option<i64> {
+        let midnight = self.day.date(year)?.and_hms_opt(0, 0, 0)?;
+        let local = midnight + Duration::seconds(i64::from(self.time));
+        Some(local.and_utc().timestamp() - i64::from(utc_offset))
+    }
+}
+
+impl PosixTz {
+    /// The local time type in effect at `timestamp` seconds since the epoch.
+    pub fn local_time_type(&self, timestamp: i64) -> LocalTimeType {
+        let Some(dst) = &self.dst else {
+            return self.std.clone();
+        };
+
+        let year = DateTime::from_timestamp(timestamp, 0).map_or(1970, |d| d.year());
+
+        // NOTE: the last transition before `timestamp` may fall in the previous year.
+        let mut last: Option<(i64, bool)> = None;
+
+        for year in year - 1..=year + 1 {
+            let start = dst.start.timestamp(year, self.std.utc_offset);
+            let end = dst.end.timestamp(year, dst.local_time_type.utc_offset);
+
+            for (transition, is_dst) in [(start, true), (end, false)] {
+                if let Some(transition) = transition
+                    && transition <= timestamp
+                    && last.is_none_or(|(t, _)| transition >= t)
+                {
+                    last = Some((transition, is_dst));
+                }
+            }
+        }
+
+        match last {
+            Some((_, true)) => dst.local_time_type.clone(),
+            _ => self.std.clone(),
+        }
+    }
+}
+
+impl std::str::FromStr for PosixTz {
+    type Err = TzifError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = PosixParser { s, pos: 0 };
+        let tz = parser.tz()?;
+
+        if parser.pos != s.len() {
+            return Err(parser_error(format!(
+                "unexpected `{}` in TZ string `{s}`",
+                &s[parser.pos..]
+            )));
+        }
+
+        Ok(tz)
+    }
+}
+
+struct PosixParser<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl PosixParser<'_> {
+    fn error(&self, expected: &str) -> TzifError {
+        parser_error(format!(
+            "expected {expected} at position {} of TZ string `{}`",
+            self.pos, self.s
+        ))
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.s.as_bytes().get(self.pos).copied()
+    }
+
+    fn eat(&mut self, b: u8) -> bool {
+        let is_next = self.peek() == Some(b);
+        if is_next {
+            self.pos += 1;
+        }
+        is_next
+    }
+
+    fn tz(&mut self) -> Result<PosixTz, TzifError> {
+        let std_name = self.name()?;
+        // POSIX offsets are west of UTC.
+        let std_offset = -self.offset()?;
+
+        let std = LocalTimeType {
+            utc_offset: std_offset,
+            is_dst: false,
+            abbreviation: std_name,
+        };
+
+        if self.peek().is_none() {
+            return Ok(PosixTz { std, dst: None });
+        }
+
+        let dst_name = self.name()?;
+        let dst_offset = match self.peek() {
+            Some(b',') | None => std_offset + 3600,
+            _ => -self.offset()?,
+        };
+
+        if !self.eat(b',') {
+            return Err(self.error("`,` followed by the start of DST"));
+        }
+        let start = self.rule()?;
+
+        if !self.eat(b',') {
+            return Err(self.error("`,` followed by the end of DST"));
+        }
+        let end = self.rule()?;
+
+        Ok(PosixTz {
+            std,
+            dst: Some(PosixDst {
+                local_time_type: LocalTimeType {
+                    utc_offset: dst_offset,
+                    is_dst: true,
+                    abbreviation: dst_name,
+                },
+                start,
+                end,
+            }),
+        })
+    }
+
+    /// An abbreviation, either alphabetic or quoted in angle brackets like `<+0330>`.
+    fn name(&mut self) -> Result<String, TzifError> {
+        let start = self.pos;
+
+        if self.eat(b'<') {
+            while self.peek().is_some_and(|b| b != b'>') {
+                self.pos += 1;
+            }
+            if !self.eat(b'>') {
+                return Err(self.error("`>`"));
+            }
+            return Ok(self.s[start + 1..self.pos - 1].to_string());
+        }
+
+        while self.peek().is_some_and(|b| b.is_ascii_alphabetic()) {
+            self.pos += 1;
+        }
+
+        if self.pos - start < 3 {
+            return Err(self.error("an abbreviation of at least three letters"));
+        }
+
+        Ok(self.s[start..self.pos].to_string())
+    }
+
+    fn number(&mut self) -> Result<i32, TzifError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        self.s[start..self.pos]
+            .parse()
+            .map_err(|_| self.error("a number"))
+    }
+
+    /// `[+-]hh[:mm[:ss]]` in seconds.
+    fn offset(&mut self) -> Result<i32, TzifError> {
+        let sign = if self.eat(b'-') {
+            -1
+        } else {
+            self.eat(b'+');
+            1
+        };
+
+        let mut seconds = self.number()? * 3600;
+
+        if self.eat(b':') {
+            seconds += self.number()? * 60;
+            if self.eat(b':') {
+                seconds += self.number()?;
+            }
+        }
+
+        Ok(sign * seconds)
+    }
+
+    fn rule(&mut self) -> Result<TransitionRule, TzifError> {
+        let day = if self.eat(b'J') {
+            match self.number()? {
+                n @ 1..=365 => RuleDay::Julian1(n as u16),
+                _ => return Err(self.error("a day from 1 to 365")),
+            }
+        } else if self.eat(b'M') {
+            let month = self.number()?;
+            if !self.eat(b'.') {
+                return Err(self.error("`.`"));
+            }
+            let week = self.number()?;
+            if !self.eat(b'.') {
+                return Err(self.error("`.`"));
+            }
+            let weekday = self.number()?;
+
+            if !(1..=12).contains(&month) || !(1..=5).contains(&week) || !(0..=6).contains(&weekday)
+            {
+                return Err(self.error("a valid `Mm.w.d` rule"));
+            }
+
+            RuleDay::MonthWeekday {
+                month: month as u32,
+                week: week as u32,
+                weekday: weekday as u32,
+            }
+        } else {
+            match self.number()? {
+                n @ 0..=365 => RuleDay::Julian0(n as u16),
+                _ => return Err(self.error("a day from 0 to 365")),
+            }
+        };
+
+        let time = if self.eat(b'/') { self.offset()? } else { 7200 };
+
+        if !(-167 * 3600..=167 * 3600).contains(&time) {
+            return Err(self.error("a time from -167 to 167 hours"));
+        }
+
+        Ok(TransitionRule { day, time })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use chrono::{LocalResult, NaiveDate, NaiveDateTime, TimeZone};
+    use chrono_tz::Tz;
+
+    use super::{PosixTz, RuleDay, TzifZone};
+
+    fn local(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn utc(s: &str) -> i64 {
+        local(s).and_utc().timestamp()
+    }
+
+    /// Builds a TZif file of `version` holding `transitions` between `types` and a `footer`.
+    fn tzif(
+        version: u8,
+        transitions: &[(i64, u8)],
+        types: &[(i32, bool, &str)],
+        footer: &str,
+    ) -> Vec<u8> {
+        let mut abbreviations = vec![];
+        let mut records = vec![];
+
+        for (offset, is_dst, abbreviation) in types {
+            records.extend(offset.to_be_bytes());
+            records.push(u8::from(*is_dst));
+            records.push(abbreviations.len() as u8);
+            abbreviations.extend(abbreviation.as_bytes());
+            abbreviations.push(0);
+        }
+
+        let block = |time_size: usize| {
+            let mut bytes = b"TZif".to_vec();
+            bytes.push(if version == 1 { 0 } else { b'0' + version });
+            bytes.extend([0; 15]);
+            for count in [0, 0, 0, transitions.len(), types.len(), abbreviations.len()] {
+                bytes.extend((count as u32).to_be_bytes());
+            }
+            for (time, _) in transitions {
+                match time_size {
+                    4 => bytes.extend((*time as i32).to_be_bytes()),
+                    _ => bytes.extend(time.to_be_bytes()),
+                }
+            }
+            bytes.extend(transitions.iter().map(|(_, i)| i));
+            bytes.extend(&records);
+            bytes.extend(&abbreviations);
+            bytes
+        };
+
+        let mut bytes = block(4);
+        if version > 1 {
+            bytes.extend(block(8));
+            bytes.extend(format!("\n{footer}\n").as_bytes());
+        }
+        bytes
+    }
+
+    fn berlin() -> TzifZone {
+        let bytes = tzif(
+            2,
+            &[(utc("2021-03-28 01:00"), 1), (utc("2021-10-31 01:00"), 0)],
+            &[(3600, false, "CET"), (7200, true, "CEST")],
+            "CET-1CEST,M3.5.0,M10.5.0/3",
+        );
+        TzifZone::parse(&bytes).unwrap()
+    }
+
+    #[test]
+    fn reads_transitions() {
+        let zone = berlin();
+        assert_eq!(zone.version, 2);
+        assert_eq!(zone.utc_offset(utc("2021-01-01 00:00")), 3600);
+        assert_eq!(
+            zone.local_time_type(utc("2021-07-01 00:00")).abbreviation,
+            "CEST"
+        );
+        assert_eq!(zone.utc_offset(utc("2021-10-31 00:59")), 7200);
+        assert_eq!(zone.utc_offset(utc("2021-10-31 01:00")), 3600);
+    }
+
+    #[test]
+    fn reads_version_1_files() {
+        let bytes = tzif(
+            1,
+            &[(0, 1)],
+            &[(0, false, "LMT"), (-18000, false, "EST")],
+            "",
+        );
+        let zone = TzifZone::parse(&bytes).unwrap();
+        assert_eq!(zone.utc_offset(-1), 0);
+        assert_eq!(zone.utc_offset(0), -18000);
+    }
+
+    #[test]
+    fn uses_footer_after_last_transition() {
+        let zone = berlin();
+        assert_eq!(zone.utc_offset(utc("2030-03-31 00:59")), 3600);
+        assert_eq!(zone.utc_offset(utc("2030-03-31 01:00")), 7200);
+        assert_eq!(zone.utc_offset(utc("2030-10-27 01:00")), 3600);
+    }
+
+    #[test]
+    fn resolves_local_times() {
+        let zone = berlin();
+
+        let date = zone
+            .from_local_datetime(&local("2021-06-01 12:00"))
+            .unwrap();
+        assert_eq!(date.to_rfc3339(), "2021-06-01T12:00:00+02:00");
+
+        assert_eq!(
+            zone.from_local_datetime(&local("2021-03-28 02:30")),
+            LocalResult::None
+        );
+
+        let LocalResult::Ambiguous(earlier, later) =
+            zone.from_local_datetime(&local("2030-10-27 02:30"))
+        else {
+            panic!("expected an ambiguous local time");
+        };
+        assert_eq!(earlier.to_rfc3339(), "2030-10-27T02:30:00+02:00");
+        assert_eq!(later.to_rfc3339(), "2030-10-27T02:30:00+01:00");
+    }
+
+    #[test]
+    fn parses_posix_tz_strings() {
+        let tz: PosixTz = "<+0330>-3:30".parse().unwrap();
+        assert_eq!(tz.std.utc_offset, 12600);
+        assert_eq!(tz.std.abbreviation, "+0330");
+        assert!(tz.dst.is_none());
+
+        // version 3 allows times beyond 24 hours, e.g. for Greenland.
+        let tz: PosixTz = "<-02>2<-01>,M3.5.0/-1,M10.5.0/0".parse().unwrap();
+        let dst = tz.dst.unwrap();
+        assert_eq!(dst.local_time_type.utc_offset, -3600);
+        assert_eq!(dst.start.time, -3600);
+
+        let tz: PosixTz = "EST5EDT,0/0,J365/25".parse().unwrap();
+        assert_eq!(tz.dst.unwrap().end.day, RuleDay::Julian1(365));
+
+        assert!("EST".parse::<PosixTz>().is_err());
+        assert!("CET-1CEST".parse::<PosixTz>().is_err());
+        assert!("CET-1CEST,M13.5.0,M10.5.0".parse::<PosixTz>().is_err());
+    }
+
+    #[test]
+    fn computes_rule_days() {
+        let last_sunday = RuleDay::MonthWeekday {
+            month: 3,
+            week: 5,
+            weekday: 0,
+        };
+        assert_eq!(last_sunday.date(2024), NaiveDate::from_ymd_opt(2024, 3, 31));
+        assert_eq!(
+            RuleDay::Julian1(60).date(2024),
+            NaiveDate::from_ymd_opt(2024, 3, 1)
+        );
+        assert_eq!(
+            RuleDay::Julian0(59).date(2024),
+            NaiveDate::from_ymd_opt(2024, 2, 29)
+        );
+    }
+
+    #[test]
+    fn handles_southern_hemisphere_footers() {
+        let tz: PosixTz = "AEST-10AEDT,M10.1.0,M4.1.0/3".parse().unwrap();
+        assert_eq!(
+            tz.local_time_type(utc("2024-01-15 00:00")).utc_offset,
+            39600
+        );
+        assert_eq!(
+            tz.local_time_type(utc("2024-07-15 00:00")).utc_offset,
+            36000
+        );
+    }
+
+    #[test]
+    fn agrees_with_installed_zones() {
+        let root = Path::new("/usr/share/zoneinfo");
+        if !root.exists() {
+            return;
+        }
+
+        // NOTE: zones that did not change recently, the installed tzdb may differ from the bundled one.
+        for name in ["Europe/Berlin", "America/New_York", "Australia/Sydney"] {
+            let zone = TzifZone::read(root.join(name)).unwrap();
+            let tz: Tz = name.parse().unwrap();
+
+            for timestamp in (0..4_000_000_000).step_by(86_400 * 7 + 3_607) {
+                let expected = tz.timestamp_opt(timestamp, 0).unwrap();
+                let actual = zone.from_timestamp(timestamp).unwrap();
+                assert_eq!(
+                    actual.naive_local(),
+                    expected.naive_local(),
+                    "{name} at {timestamp}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,184 @@
+/// Decoding of UTF-16 which keeps unpaired surrogates, e.g. of Windows file names.
+///
+/// Text is held as [`Wtf8`], UTF-8 extended with the 3-byte encodings of unpaired surrogates, so
+/// that any sequence of 16-bit units decodes and encodes back to the same units.
+use std::fmt;
+
+use crate::utils::utf8::{self, Problem, Strictness};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ByteOrder {
+    Little,
+    Big,
+}
+
+/// The 16-bit units of `bytes`, `None` for an odd number of bytes.
+pub fn units(bytes: &[u8], order: ByteOrder) -> Option<Vec<u16>> {
+    let chunks = bytes.chunks_exact(2);
+    if !chunks.remainder().is_empty() {
+        return None;
+    }
+
+    let units = chunks.map(|c| match order {
+        ByteOrder::Little => u16::from_le_bytes([c[0], c[1]]),
+        ByteOrder::Big => u16::from_be_bytes([c[0], c[1]]),
+    });
+    Some(units.collect())
+}
+
+/// Decode the UTF-16 of `bytes`, without a byte order mark.
+pub fn decode(bytes: &[u8], order: ByteOrder) -> Option<Wtf8> {
+    Some(Wtf8::from_utf16(&units(bytes, order)?))
+}
+
+pub fn encode(text: &Wtf8, order: ByteOrder) -> Vec<u8> {
+    text.to_utf16()
+        .into_iter()
+        .flat_map(|unit| match order {
+            ByteOrder::Little => unit.to_le_bytes(),
+            ByteOrder::Big => unit.to_be_bytes(),
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Wtf8 {
+    bytes: Vec<u8>,
+}
+
+impl Wtf8 {
+    pub fn from_utf16(units: &[u16]) -> Wtf8 {
+        let mut bytes = vec![];
+        for decoded in char::decode_utf16(units.iter().copied()) {
+            match decoded {
+                Ok(c) => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+                Err(e) => {
+                    // The 3 bytes of a code point from U+0800 to U+FFFF
+                    let s = e.unpaired_surrogate();
+                    bytes.extend([
+                        0xE0 | (s >> 12) as u8,
+                        0x80 | ((s >> 6) & 0x3F) as u8,
+                        0x80 | (s & 0x3F) as u8,
+                    ]);
+                }
+            }
+        }
+
+        Wtf8 { bytes }
+    }
+
+    /// `None` unless `bytes` are UTF-8 but for unpaired surrogates. A surrogate pair has to be
+    /// encoded as its supplementary code point, in 4 bytes.
+    pub fn from_bytes(bytes: Vec<u8>) -> Option<Wtf8> {
+        let decoded = utf8::decode(&bytes, Strictness::Rfc3629);
+        let only_surrogates = decoded
+            .diagnostics
+            .iter()
+            .all(|d| matches!(d.problem, Problem::Surrogate(_)));
+        let paired = decoded.code_points.windows(2).any(|pair| {
+            utf8::HIGH_SURROGATES.contains(&pair[0]) && utf8::LOW_SURROGATES.contains(&pair[1])
+        });
+
+        (only_surrogates && !paired).then_some(Wtf8 { bytes })
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// The text, unless it has unpaired surrogates.
+    pub fn as_str(&self) -> Option<&str> {
+        std::str::from_utf8(&self.bytes).ok()
+    }
+
+    /// The code points, unpaired surrogates included.
+    pub fn code_points(&self) -> Vec<u32> {
+        utf8::decode(&self.bytes, Strictness::Rfc2279).code_points
+    }
+
+    pub fn to_utf16(&self) -> Vec<u16> {
+        let mut units = vec![];
+        for code_point in self.code_points() {
+            match char::from_u32(code_point) {
+                Some(c) => units.extend_from_slice(c.encode_utf16(&mut [0; 2])),
+                None => units.push(code_point as u16),
+            }
+        }
+
+        units
+    }
+
+    /// The text, with U+FFFD for unpaired surrogates.
+    pub fn to_string_lossy(&self) -> String {
+        self.code_points()
+            .into_iter()
+            .map(|c| char::from_u32(c).unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect()
+    }
+}
+
+impl From<&str> for Wtf8 {
+    fn from(text: &str) -> Wtf8 {
+        Wtf8 {
+            bytes: text.as_bytes().to_vec(),
+        }
+    }
+}
+
+impl fmt::Display for Wtf8 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string_lossy())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode, encode, ByteOrder, Wtf8};
+
+    #[test]
+    fn keeps_unpaired_surrogates() {
+        // `a`, a lone low surrogate, U+1F600 as a pair, then a dangling high surrogate
+        let units = [0x61, 0xDC00, 0xD83D, 0xDE00, 0xD800];
+        let text = Wtf8::from_utf16(&units);
+        assert_eq!(
+            text.as_bytes(),
+            b"a\xED\xB0\x80\xF0\x9F\x98\x80\xED\xA0\x80"
+        );
+        assert_eq!(text.code_points(), [0x61, 0xDC00, 0x1F600, 0xD800]);
+        assert_eq!(text.as_str(), None);
+        assert_eq!(text.to_string(), "a\u{FFFD}😀\u{FFFD}");
+        assert_eq!(text.to_utf16(), units);
+
+        let text = Wtf8::from("naïve 😀");
+        assert_eq!(text.as_str(), Some("naïve 😀"));
+        assert_eq!(String::from_utf16(&text.to_utf16()).unwrap(), "naïve 😀");
+    }
+
+    #[test]
+    fn round_trips_bytes() {
+        // A Windows file name with an unpaired surrogate
+        let le = b"f\x00\x00\xD8.\x00t\x00x\x00t\x00";
+        let text = decode(le, ByteOrder::Little).unwrap();
+        assert_eq!(encode(&text, ByteOrder::Little), le);
+
+        let be = encode(&text, ByteOrder::Big);
+        assert_eq!(be, b"\x00f\xD8\x00\x00.\x00t\x00x\x00t");
+        assert_eq!(decode(&be, ByteOrder::Big), Some(text));
+        assert_eq!(decode(b"odd", ByteOrder::Little), None);
+    }
+
+    #[test]
+    fn validates_bytes() {
+        let bytes = b"a\xED\xA0\x80".to_vec();
+        assert_eq!(Wtf8::from_bytes(bytes.clone()).unwrap().as_bytes(), bytes);
+
+        // A surrogate pair in 3 bytes each, an overlong and a truncated sequence
+        assert_eq!(Wtf8::from_bytes(b"\xED\xA0\xBD\xED\xB8\x80".to_vec()), None);
+        assert_eq!(Wtf8::from_bytes(b"\xC0\xAF".to_vec()), None);
+        assert_eq!(Wtf8::from_bytes(b"\xE2\x82".to_vec()), None);
+    }
+}
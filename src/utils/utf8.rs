@@ -0,0 +1,320 @@
+/// A lenient decoder of UTF-8 and of its variants, which decodes what it can and reports the
+/// rest.
+///
+/// [`decode`] reads sequences of up to 6 bytes as in the original UTF-8 of RFC 2279, and reports
+/// whatever the chosen [`Strictness`] forbids, e.g. overlong sequences, encoded surrogates or code
+/// points above U+10FFFF, along with the bytes no variant can decode.
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Strictness {
+    /// UTF-8 as of RFC 3629, up to U+10FFFF without surrogates.
+    Rfc3629,
+    /// The original UTF-8 of RFC 2279, up to 6 bytes for 31-bit code points, surrogates included.
+    Rfc2279,
+    /// Supplementary code points as surrogate pairs of 3 bytes each, e.g. as by Oracle.
+    Cesu8,
+    /// CESU-8 with U+0000 as the overlong `C0 80`, e.g. as by Java's `DataOutput`.
+    Modified,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Problem {
+    /// A code point in more bytes than needed.
+    Overlong(u32),
+    /// A surrogate in 3 bytes, or a lone one in CESU-8.
+    Surrogate(u32),
+    /// A code point above U+10FFFF, or in 5 or 6 bytes.
+    OutOfRange(u32),
+    /// A supplementary code point in 4 bytes rather than as a surrogate pair.
+    Supplementary(u32),
+    /// A raw `00` byte, which Modified UTF-8 writes as `C0 80`.
+    Nul,
+    /// A leading byte followed by too few continuation bytes, skipped with them.
+    Truncated,
+    /// A continuation byte outside of a sequence, skipped.
+    StrayContinuation,
+    /// `FE` or `FF`, which start no sequence, skipped.
+    InvalidByte,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::Overlong(c) => write!(f, "overlong encoding of U+{c:04X}"),
+            Problem::Surrogate(c) => write!(f, "encoded surrogate U+{c:04X}"),
+            Problem::OutOfRange(c) => write!(f, "code point U+{c:04X} out of range"),
+            Problem::Supplementary(c) => write!(f, "supplementary U+{c:04X} not in surrogates"),
+            Problem::Nul => write!(f, "raw NUL byte"),
+            Problem::Truncated => write!(f, "truncated sequence"),
+            Problem::StrayContinuation => write!(f, "stray continuation byte"),
+            Problem::InvalidByte => write!(f, "invalid byte"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    /// The offset of the first byte of the sequence, and its number of bytes.
+    pub offset: usize,
+    pub len: usize,
+    pub problem: Problem,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at byte {}", self.problem, self.offset)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Decoded {
+    /// The decoded code points, including the reported ones but not the skipped bytes.
+    pub code_points: Vec<u32>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Decoded {
+    pub fn is_valid(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// The text of the code points, with U+FFFD for those which are not characters.
+    pub fn to_string_lossy(&self) -> String {
+        self.code_points
+            .iter()
+            .map(|&c| char::from_u32(c).unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+// The smallest code point of each length of sequence
+const MINIMUMS: [u32; 7] = [0, 0, 0x80, 0x800, 0x10000, 0x20_0000, 0x400_0000];
+
+pub const SURROGATES: std::ops::RangeInclusive<u32> = 0xD800..=0xDFFF;
+pub const HIGH_SURROGATES: std::ops::RangeInclusive<u32> = 0xD800..=0xDBFF;
+pub const LOW_SURROGATES: std::ops::RangeInclusive<u32> = 0xDC00..=0xDFFF;
+
+fn is_continuation(byte: &u8) -> bool {
+    byte & 0b1100_0000 == 0b1000_0000
+}
+
+/// The number of bytes of the sequence starting with `lead`.
+fn sequence_len(lead: u8) -> Result<usize, Problem> {
+    match lead {
+        0x00..=0x7F => Ok(1),
+        0x80..=0xBF => Err(Problem::StrayContinuation),
+        0xC0..=0xDF => Ok(2),
+        0xE0..=0xEF => Ok(3),
+        0xF0..=0xF7 => Ok(4),
+        0xF8..=0xFB => Ok(5),
+        0xFC..=0xFD => Ok(6),
+        0xFE..=0xFF => Err(Problem::InvalidByte),
+    }
+}
+
+/// What `strictness` forbids of the code point of a valid sequence of `len` bytes.
+fn check(code_point: u32, len: usize, strictness: Strictness) -> Option<Problem> {
+    let pairs = matches!(strictness, Strictness::Cesu8 | Strictness::Modified);
+
+    if code_point < MINIMUMS[len] {
+        if strictness == Strictness::Modified && code_point == 0 && len == 2 {
+            return None;
+        }
+        return Some(Problem::Overlong(code_point));
+    }
+
+    match code_point {
+        0 if strictness == Strictness::Modified => Some(Problem::Nul),
+        _ if strictness == Strictness::Rfc2279 => None,
+        c if len > 4 || c > 0x10FFFF => Some(Problem::OutOfRange(c)),
+        c if SURROGATES.contains(&c) => Some(Problem::Surrogate(c)),
+        c if len == 4 && pairs => Some(Problem::Supplementary(c)),
+        _ => None,
+    }
+}
+
+/// Decode `bytes`, reporting what `strictness` forbids.
+///
+/// Sequences forbidden but well-formed, e.g. overlong, are still decoded. Truncated sequences,
+/// stray continuation bytes and invalid bytes are skipped.
+pub fn decode(bytes: &[u8], strictness: Strictness) -> Decoded {
+    let pairs = matches!(strictness, Strictness::Cesu8 | Strictness::Modified);
+    let mut decoded = Decoded::default();
+
+    // A high surrogate just decoded in CESU-8, reported unless a low one follows
+    let mut pending: Option<Diagnostic> = None;
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let len = match sequence_len(bytes[i]) {
+            Ok(len) => len,
+            Err(problem) => {
+                decoded.diagnostics.extend(pending.take());
+                decoded.diagnostics.push(Diagnostic {
+                    offset: i,
+                    len: 1,
+                    problem,
+                });
+                i += 1;
+                continue;
+            }
+        };
+
+        let continuations = bytes[i + 1..]
+            .iter()
+            .take(len - 1)
+            .take_while(|b| is_continuation(b))
+            .count();
+        if continuations < len - 1 {
+            decoded.diagnostics.extend(pending.take());
+            decoded.diagnostics.push(Diagnostic {
+                offset: i,
+                len: continuations + 1,
+                problem: Problem::Truncated,
+            });
+            i += continuations + 1;
+            continue;
+        }
+
+        // The low bits of the leading byte, then 6 bits per continuation byte
+        let mask = if len == 1 { 0x7F } else { 0x7F >> len };
+        let lead = u32::from(bytes[i] & mask);
+        let code_point = bytes[i + 1..i + len]
+            .iter()
+            .fold(lead, |c, b| (c << 6) | u32::from(b & 0b0011_1111));
+        let problem = check(code_point, len, strictness);
+
+        // NOTE: in CESU-8 a low surrogate right after a high one completes a pair
+        if pairs && len == 3 && LOW_SURROGATES.contains(&code_point) && pending.is_some() {
+            pending = None;
+            let high = decoded.code_points.pop().unwrap();
+            decoded
+                .code_points
+                .push(0x10000 + ((high - 0xD800) << 10) + (code_point - 0xDC00));
+            i += len;
+            continue;
+        }
+
+        decoded.diagnostics.extend(pending.take());
+        let diagnostic = problem.map(|problem| Diagnostic {
+            offset: i,
+            len,
+            problem,
+        });
+        if pairs && len == 3 && HIGH_SURROGATES.contains(&code_point) {
+            pending = diagnostic;
+        } else {
+            decoded.diagnostics.extend(diagnostic);
+        }
+
+        decoded.code_points.push(code_point);
+        i += len;
+    }
+
+    decoded.diagnostics.extend(pending);
+    decoded
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode, Diagnostic, Problem, Strictness};
+
+    fn problems(bytes: &[u8], strictness: Strictness) -> Vec<(usize, Problem)> {
+        decode(bytes, strictness)
+            .diagnostics
+            .iter()
+            .map(|d| (d.offset, d.problem))
+            .collect()
+    }
+
+    #[test]
+    fn decodes_valid_utf8() {
+        let decoded = decode("aé€𝄞".as_bytes(), Strictness::Rfc3629);
+        assert!(decoded.is_valid());
+        assert_eq!(decoded.code_points, [0x61, 0xE9, 0x20AC, 0x1D11E]);
+        assert_eq!(decoded.to_string_lossy(), "aé€𝄞");
+    }
+
+    #[test]
+    fn reports_extended_sequences() {
+        // U+7FFFFFFF in 6 bytes and U+3FFFFFF in 5 bytes
+        let bytes = b"\xFD\xBF\xBF\xBF\xBF\xBF\xFB\xBF\xBF\xBF\xBF";
+        let decoded = decode(bytes, Strictness::Rfc2279);
+        assert!(decoded.is_valid());
+        assert_eq!(decoded.code_points, [0x7FFF_FFFF, 0x3FF_FFFF]);
+
+        let decoded = decode(bytes, Strictness::Rfc3629);
+        assert_eq!(decoded.code_points, [0x7FFF_FFFF, 0x3FF_FFFF]);
+        assert_eq!(
+            decoded.diagnostics[0],
+            Diagnostic {
+                offset: 0,
+                len: 6,
+                problem: Problem::OutOfRange(0x7FFF_FFFF)
+            }
+        );
+        assert_eq!(decoded.to_string_lossy(), "\u{FFFD}\u{FFFD}");
+    }
+
+    #[test]
+    fn reports_malformed_bytes() {
+        let bytes = b"\xC0\xAFa\x80\xE2\x82\xFF\xF0\x9F";
+        assert_eq!(
+            problems(bytes, Strictness::Rfc3629),
+            [
+                (0, Problem::Overlong(0x2F)),
+                (3, Problem::StrayContinuation),
+                (4, Problem::Truncated),
+                (6, Problem::InvalidByte),
+                (7, Problem::Truncated),
+            ]
+        );
+        assert_eq!(decode(bytes, Strictness::Rfc3629).code_points, [0x2F, 0x61]);
+    }
+
+    #[test]
+    fn pairs_surrogates_in_cesu8() {
+        // U+1F600 as a surrogate pair, then a lone high surrogate
+        let bytes = b"\xED\xA0\xBD\xED\xB8\x80\xED\xA0\xBDx";
+        let decoded = decode(bytes, Strictness::Cesu8);
+        assert_eq!(decoded.code_points, [0x1F600, 0xD83D, 0x78]);
+        assert_eq!(
+            problems(bytes, Strictness::Cesu8),
+            [(6, Problem::Surrogate(0xD83D))]
+        );
+
+        assert_eq!(
+            problems(bytes, Strictness::Rfc3629),
+            [
+                (0, Problem::Surrogate(0xD83D)),
+                (3, Problem::Surrogate(0xDE00)),
+                (6, Problem::Surrogate(0xD83D)),
+            ]
+        );
+        assert_eq!(
+            problems("😀".as_bytes(), Strictness::Cesu8),
+            [(0, Problem::Supplementary(0x1F600))]
+        );
+    }
+
+    #[test]
+    fn accepts_modified_nul() {
+        let decoded = decode(b"a\xC0\x80b", Strictness::Modified);
+        assert!(decoded.is_valid());
+        assert_eq!(decoded.code_points, [0x61, 0, 0x62]);
+
+        assert_eq!(
+            problems(b"a\x00", Strictness::Modified),
+            [(1, Problem::Nul)]
+        );
+        assert_eq!(
+            problems(b"\xC0\x80", Strictness::Cesu8),
+            [(0, Problem::Overlong(0))]
+        );
+    }
+}